@@ -31,7 +31,7 @@ export async function getAllCampaigns() {
       fee: StellarSdk.BASE_FEE,
       networkPassphrase: NETWORK_PASSPHRASE,
     })
-      .addOperation(contract.call('get_all_campaigns'))
+      .addOperation(contract.call('get_all_campaigns', StellarSdk.nativeToScVal(false)))
       .setTimeout(30)
       .build();
 
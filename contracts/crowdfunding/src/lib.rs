@@ -8,20 +8,82 @@
 //! - Event emission for indexing
 //! - Inter-contract call to RewardToken (SST) on donation
 //! - Deadline expiration logic
+//! - Multi-asset campaigns valued through a SEP-40 price oracle
+//! - Recurring subscription donations via token allowances
+//! - Donation messages and anonymous donations
+//! - Gift donations funded by a different payer
+//! - Sponsor matching pools
+//! - Quadratic funding rounds
+//! - Creator withdrawals with platform fee collection
+//! - Donation retraction during a grace period
+//! - Refunds for failed campaigns with SST clawback
+//! - Vesting SST rewards for large donations
+//! - Pledge tiers with limited perks
+//! - Perk fulfilment tracking with donor confirmation
+//! - On-chain creator reputation stats
+//! - Co-creator teams with split payouts
+//! - Multisig-controlled withdrawals
+//! - Streaming payouts with pro-rata refunds on suspension
+//! - Donor disputes with arbiter resolution
+//! - Verifier attestations for creator KYC and charity status
+//! - Anti-spam creator bonds, slashable to compensate donors
+//! - Private campaigns with creator-managed or Merkle allowlists
+//! - Admin parameters (fee, reward rate, pausing, curators) for governance
 
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short,
-    Address, Env, String, Vec, log, contractclient,
+    contract, contractimpl, contracttype, symbol_short, token,
+    xdr::ToXdr, Address, Bytes, BytesN, Env, Map, String, Symbol, Vec, log, contractclient,
 };
 
 /// External RewardToken contract client for inter-contract calls
 #[contractclient(name = "RewardTokenClient")]
 pub trait RewardTokenInterface {
-    fn mint(env: Env, to: Address, amount: i128);
+    fn mint_up_to(env: Env, minter: Address, to: Address, amount: i128) -> i128;
+    fn mint_vested(
+        env: Env,
+        minter: Address,
+        to: Address,
+        amount: i128,
+        cliff_ledgers: u32,
+        duration_ledgers: u32,
+    ) -> i128;
+    fn burn(env: Env, from: Address, amount: i128);
+    fn clawback(env: Env, minter: Address, from: Address, amount: i128) -> i128;
     fn balance(env: Env, account: Address) -> i128;
 }
 
+/// SEP-40 asset identifier
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// SEP-40 price record
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// External SEP-40 price oracle client (subset used for valuation)
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracleInterface {
+    fn decimals(env: Env) -> u32;
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+}
+
+/// Price oracle configuration
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct OracleConfig {
+    pub oracle: Address,
+    pub max_age: u64, // Maximum price age in seconds
+}
+
 /// Campaign lifecycle status
 #[contracttype]
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -30,6 +92,7 @@ pub enum CampaignStatus {
     Funded,    // Target reached
     Closed,    // Manually closed
     Expired,   // Deadline passed without reaching goal
+    Suspended, // Halted by the admin; remaining funds refundable
 }
 
 /// Campaign data structure
@@ -45,13 +108,234 @@ pub struct Campaign {
     pub end_time: u64,
 }
 
+/// Co-creator and their share of campaign payouts
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct TeamMember {
+    pub member: Address,
+    pub share_bps: u32,
+}
+
 /// Individual donation record
 #[contracttype]
 #[derive(Clone)]
 pub struct Donation {
     pub donor: Address,
+    pub payer: Address,         // Funded the donation; retractions and refunds go here
     pub amount: i128,
     pub timestamp: u64,
+    pub asset: Option<Address>, // None for bookkeeping-only donations
+    pub value: i128,            // Amount credited towards the target
+    pub message: Option<String>,
+    pub anonymous: bool,        // Donor hidden from public queries
+    pub reward: i128,           // SST minted for this donation
+    pub vested: bool,           // Reward minted into a vesting schedule
+    pub tier: Option<u32>,      // Selected pledge tier
+    pub matches: Map<u64, i128>, // Pool ID -> sponsor match paid in the donation's asset
+    pub match_value: i128,      // Sponsor match credited towards the target
+}
+
+/// Delivery state of a donation's perk
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FulfilmentStatus {
+    Pending,   // Awaiting shipment
+    Shipped,   // Creator reports delivery
+    Confirmed, // Donor confirmed receipt
+    Disputed,  // Donor reports a problem
+}
+
+/// Aggregated track record of a creator
+/// Fulfilment counters hold the number of perks currently in each state
+#[contracttype]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct CreatorStats {
+    pub campaigns_launched: u32,
+    pub campaigns_funded: u32,
+    pub campaigns_failed: u32, // Expired, or closed before funding
+    pub total_raised: i128,    // Value raised by funded campaigns, net of refunds
+    pub refunds_issued: u32,
+    pub campaigns_suspended: u32,
+    pub perks_owed: u32,       // Tier perks of funded campaigns; those not in a state below are pending
+    pub perks_shipped: u32,
+    pub perks_confirmed: u32,
+    pub perks_disputed: u32,
+}
+
+/// Pledge tier offering a perk to donors giving at least `min_amount`
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct RewardTier {
+    pub id: u32,
+    pub min_amount: i128,
+    pub description_hash: BytesN<32>, // Hash of the off-chain perk description
+    pub quantity: u32,                // Max selections, 0 = unlimited
+    pub claimed: u32,
+}
+
+/// Rewards for donations valued at or above `threshold` vest instead of
+/// being minted liquid
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct VestingPolicy {
+    pub threshold: i128,
+    pub cliff_ledgers: u32,
+    pub duration_ledgers: u32,
+}
+
+/// Platform fee configuration
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct FeeConfig {
+    pub fee_bps: u32, // Basis points deducted from creator payouts
+    pub treasury: Address,
+}
+
+/// Recurring donation pledge
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Subscription {
+    pub id: u64,
+    pub campaign_id: u64,
+    pub donor: Address,
+    pub asset: Address,
+    pub amount: i128,
+    pub interval: u64,       // Seconds between payments
+    pub next_payment: u64,   // Earliest timestamp for the next payment
+    pub payments_made: u32,
+    pub skipped_periods: u32,
+    pub active: bool,
+}
+
+/// Sponsor pool matching donations to eligible campaigns
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct MatchingPool {
+    pub id: u64,
+    pub sponsor: Address,
+    pub asset: Address,
+    pub campaign_ids: Vec<u64>,
+    pub ratio_bps: u32,   // 10_000 = 1:1 match
+    pub cap: i128,        // Total deposited by the sponsor
+    pub matched: i128,    // Amount already paid out as matches
+    pub expires_at: u64,
+    pub reclaimed: bool,
+}
+
+/// Quadratic funding round distributing a sponsor pool across campaigns
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct QfRound {
+    pub id: u64,
+    pub sponsor: Address,
+    pub asset: Address,
+    pub pool: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub campaign_ids: Vec<u64>,
+    pub finalized: bool,
+}
+
+/// M-of-N signer set controlling a campaign's withdrawals
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct WithdrawalSigners {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// Pending multisig withdrawal of a campaign's available funds
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct WithdrawalProposal {
+    pub id: u64,
+    pub campaign_id: u64,
+    pub proposer: Address,
+    pub approvals: Vec<Address>,
+    pub expires_at: u64,
+    pub executed: bool,
+}
+
+/// Linear release of a funded campaign's raised funds
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct PayoutStream {
+    pub duration: u64,           // Seconds over which funds become claimable
+    pub started_at: Option<u64>, // Set when the campaign is funded
+    pub stopped_at: Option<u64>, // Set if the campaign is suspended
+}
+
+/// Stage of a donor dispute against a funded campaign
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DisputeStatus {
+    Gathering, // Collecting donor support below the threshold
+    Open,      // Withdrawals frozen pending arbitration
+    Released,  // Arbiters released funds to the team
+    Refunded,  // Campaign suspended and donors refunded
+}
+
+/// Donor dispute over a funded campaign
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Dispute {
+    pub campaign_id: u64,
+    pub supporters: Vec<Address>,
+    pub support: i128,  // Contribution value of supporting donors
+    pub status: DisputeStatus,
+    pub deadline: u64,  // Arbitration deadline once open
+}
+
+/// Subject a verifier can attest to
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum AttestationSubject {
+    Creator(Address), // KYC-verified creator
+    Charity(u64),     // Campaign run by a registered charity
+}
+
+/// Verifier attestation with expiry
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Attestation {
+    pub verifier: Address,
+    pub issued_at: u64,
+    pub expires_at: u64,
+    pub revoked: bool,
+}
+
+/// Verification status of a campaign, derived from live attestations
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Verification {
+    pub creator_kyc: bool,
+    pub registered_charity: bool,
+}
+
+/// Bond required from creators when launching a campaign
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct BondConfig {
+    pub asset: Address,
+    pub amount: i128,
+}
+
+/// Lifecycle of a campaign's creation bond
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BondStatus {
+    Held,     // Locked while the campaign runs
+    Refunded, // Returned after a clean end
+    Slashed,  // Forfeited to compensate donors
+}
+
+/// Bond posted for a campaign
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct CampaignBond {
+    pub asset: Address,
+    pub amount: i128,
+    pub status: BondStatus,
 }
 
 /// Storage keys
@@ -63,6 +347,68 @@ pub enum DataKey {
     Donations(u64),
     WalletDonations(Address),
     RewardTokenAddr,
+    Admin,
+    NativeAsset,
+    PriceOracle,
+    CampaignAssets(u64),
+    Raised(u64, Address),
+    Subscription(u64),
+    SubscriptionCounter,
+    MatchingPool(u64),
+    MatchingPoolCounter,
+    CampaignPools(u64),
+    QfRound(u64),
+    QfRoundCounter,
+    CampaignRounds(u64),
+    RoundDonors(u64, u64),                // (round, campaign) -> unique donors
+    RoundContribution(u64, u64, Address), // (round, campaign, donor) -> value
+    RoundMatch(u64, u64),                 // (round, campaign) -> unclaimed match
+    FeeConfig,
+    AccruedFees(Address),
+    Withdrawn(u64, Address),
+    Refunded(u64, Address),
+    RewardRate,
+    VestingPolicy,
+    RewardTiers(u64),
+    Fulfilment(u64, u32), // (campaign_id, donation index)
+    CreatorStats(Address),
+    CampaignTeam(u64),
+    WithdrawalSigners(u64),
+    WithdrawalProposal(u64),
+    WithdrawalProposalCounter,
+    PayoutStream(u64),
+    Streamed(u64, Address), // Creator entitlement frozen at suspension
+    Arbiters,
+    DisputeThreshold,
+    Dispute(u64),
+    Verifiers,
+    Attestation(AttestationSubject),
+    AckThreshold(u64),
+    VerificationAck(u64, Address),
+    BondConfig,
+    CampaignBond(u64),
+    BondClaimed(u64, Address),
+    Paused,
+    Curators,
+    FeaturedCampaigns,
+}
+
+/// Storage keys for private campaign allowlists
+/// Kept apart from `DataKey`, which is at the contract type variant limit.
+/// Per-donor `Allowlisted` flags live in persistent storage so large lists do
+/// not grow the instance entry
+#[contracttype]
+pub enum AllowlistKey {
+    PrivateCampaign(u64),
+    Allowlisted(u64, Address),
+    Root(u64),
+}
+
+/// Storage keys for campaign custody bookkeeping
+/// Kept apart from `DataKey`, which is at the contract type variant limit
+#[contracttype]
+pub enum CustodyKey {
+    HeldAssets(u64), // Every asset a campaign has taken custody of
 }
 
 #[contract]
@@ -86,375 +432,5236 @@ impl CrowdfundingContract {
         env.storage().instance().get(&DataKey::RewardTokenAddr)
     }
 
-    /// Check and mark expired campaigns
-    pub fn check_expired(env: Env, campaign_id: u64) -> bool {
-        let mut campaign: Campaign = env.storage().instance()
-            .get(&DataKey::Campaign(campaign_id))
-            .expect("Campaign not found");
-
-        if campaign.status == CampaignStatus::Open && env.ledger().timestamp() > campaign.end_time {
-            campaign.status = CampaignStatus::Expired;
-            env.storage().instance().set(&DataKey::Campaign(campaign_id), &campaign);
-            
-            env.events().publish(
-                (symbol_short!("CAMPAIGN"), symbol_short!("expired")),
-                campaign_id,
-            );
-            
-            env.storage().instance().extend_ttl(100_000, 100_000);
-            return true;
+    /// Set the platform admin (one-time setup)
+    pub fn set_admin(env: Env, admin: Address) {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Admin already set");
         }
-        false
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+        log!(&env, "Admin set");
     }
-    /// Create a new campaign
-    pub fn create_campaign(
-        env: Env,
-        creator: Address,
-        title: String,
-        target_amount: i128,
-        duration_days: u64,
-    ) -> u64 {
-        creator.require_auth();
 
-        if target_amount <= 0 {
-            panic!("Target amount must be positive");
-        }
+    /// Get the platform admin
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Admin)
+    }
 
-        let campaign_id: u64 = env.storage().instance()
-            .get(&DataKey::CampaignCounter)
-            .unwrap_or(0) + 1;
-        
-        env.storage().instance().set(&DataKey::CampaignCounter, &campaign_id);
+    /// Hand the admin role to a new address, e.g. a governance contract (admin only)
+    pub fn transfer_admin(env: Env, new_admin: Address) {
+        let admin = require_admin(&env);
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().extend_ttl(100_000, 100_000);
 
-        let end_time = env.ledger().timestamp() + (duration_days * 86400);
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("transfer")),
+            (admin, new_admin),
+        );
+    }
 
-        let campaign = Campaign {
-            id: campaign_id,
-            creator: creator.clone(),
-            title: title.clone(),
-            target_amount,
-            total_donated: 0,
-            status: CampaignStatus::Open,
-            end_time,
-        };
+    /// Set the SST minted per unit donated (admin only)
+    pub fn set_reward_rate(env: Env, rate: i128) {
+        require_admin(&env);
 
-        env.storage().instance().set(&DataKey::Campaign(campaign_id), &campaign);
+        if rate < 0 {
+            panic!("Reward rate cannot be negative");
+        }
 
-        let mut campaign_list: Vec<u64> = env.storage().instance()
-            .get(&DataKey::CampaignList)
-            .unwrap_or(Vec::new(&env));
-        campaign_list.push_back(campaign_id);
-        env.storage().instance().set(&DataKey::CampaignList, &campaign_list);
+        env.storage().instance().set(&DataKey::RewardRate, &rate);
+        env.storage().instance().extend_ttl(100_000, 100_000);
 
-        let donations: Vec<Donation> = Vec::new(&env);
-        env.storage().instance().set(&DataKey::Donations(campaign_id), &donations);
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("rate")),
+            rate,
+        );
+    }
+
+    /// Get the SST minted per unit donated
+    pub fn get_reward_rate(env: Env) -> i128 {
+        reward_rate(&env)
+    }
+
+    /// Set or clear the SST vesting policy for large donations (admin only)
+    pub fn set_vesting_policy(env: Env, policy: Option<VestingPolicy>) {
+        require_admin(&env);
 
+        match &policy {
+            Some(policy) => {
+                if policy.threshold <= 0 {
+                    panic!("Vesting threshold must be positive");
+                }
+                if policy.duration_ledgers == 0 || policy.cliff_ledgers > policy.duration_ledgers {
+                    panic!("Invalid vesting schedule");
+                }
+                env.storage().instance().set(&DataKey::VestingPolicy, policy);
+            }
+            None => env.storage().instance().remove(&DataKey::VestingPolicy),
+        }
         env.storage().instance().extend_ttl(100_000, 100_000);
 
-        // Emit CampaignCreated event
         env.events().publish(
-            (symbol_short!("CAMPAIGN"), symbol_short!("created")),
-            (campaign_id, creator, title, target_amount),
+            (symbol_short!("ADMIN"), symbol_short!("vesting")),
+            policy,
         );
+    }
 
-        log!(&env, "Campaign {} created", campaign_id);
-        
-        campaign_id
+    /// Get the SST vesting policy, if any
+    pub fn get_vesting_policy(env: Env) -> Option<VestingPolicy> {
+        env.storage().instance().get(&DataKey::VestingPolicy)
     }
 
-    /// Donate to a campaign
-    pub fn donate(env: Env, campaign_id: u64, donor: Address, amount: i128) {
-        donor.require_auth();
+    /// Suspend an open or funded campaign (admin only)
+    /// Streaming stops; the unstreamed remainder becomes refundable to donors pro-rata
+    pub fn suspend_campaign(env: Env, campaign_id: u64) {
+        require_admin(&env);
 
-        if amount <= 0 {
-            panic!("Donation amount must be positive");
+        let mut campaign = load_campaign(&env, campaign_id);
+
+        if campaign.status != CampaignStatus::Open && campaign.status != CampaignStatus::Funded {
+            panic!("Campaign cannot be suspended");
         }
 
-        let mut campaign: Campaign = env.storage().instance()
-            .get(&DataKey::Campaign(campaign_id))
-            .expect("Campaign not found");
+        suspend_campaign(&env, &mut campaign);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+    }
 
-        // STRICT ROLE SEPARATION
-        if donor == campaign.creator {
-            panic!("Creator cannot donate to their own campaign");
-        }
+    /// Appoint a dispute arbiter (admin only)
+    pub fn add_arbiter(env: Env, arbiter: Address) {
+        require_admin(&env);
 
-        // Check campaign status
-        if campaign.status != CampaignStatus::Open {
-            panic!("Campaign is not accepting donations");
+        let mut arbiters = Self::get_arbiters(env.clone());
+        if arbiters.contains(&arbiter) {
+            panic!("Already an arbiter");
         }
+        arbiters.push_back(arbiter.clone());
+        env.storage().instance().set(&DataKey::Arbiters, &arbiters);
+        env.storage().instance().extend_ttl(100_000, 100_000);
 
-        // Check end time — auto-expire if deadline passed
-        if env.ledger().timestamp() > campaign.end_time {
-            campaign.status = CampaignStatus::Expired;
-            env.storage().instance().set(&DataKey::Campaign(campaign_id), &campaign);
-            env.events().publish(
-                (symbol_short!("CAMPAIGN"), symbol_short!("expired")),
-                campaign_id,
-            );
-            panic!("Campaign has expired");
-        }
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("arbiter")),
+            (arbiter, true),
+        );
+    }
 
-        // Prevent overfunding
-        let remaining = campaign.target_amount - campaign.total_donated;
-        if amount > remaining {
-            panic!("Donation exceeds target. Overfunding not allowed");
-        }
+    /// Remove a dispute arbiter (admin only)
+    pub fn remove_arbiter(env: Env, arbiter: Address) {
+        require_admin(&env);
 
-        // Update campaign
-        campaign.total_donated = campaign.total_donated
-            .checked_add(amount)
-            .expect("Donation overflow");
+        let mut arbiters = Self::get_arbiters(env.clone());
+        let index = arbiters.first_index_of(&arbiter).expect("Not an arbiter");
+        arbiters.remove(index);
+        env.storage().instance().set(&DataKey::Arbiters, &arbiters);
+        env.storage().instance().extend_ttl(100_000, 100_000);
 
-        // Auto-mark as Funded if target reached
-        if campaign.total_donated >= campaign.target_amount {
-            campaign.status = CampaignStatus::Funded;
-            
-            // Emit CampaignFunded event
-            env.events().publish(
-                (symbol_short!("CAMPAIGN"), symbol_short!("funded")),
-                campaign_id,
-            );
-        }
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("arbiter")),
+            (arbiter, false),
+        );
+    }
 
-        env.storage().instance().set(&DataKey::Campaign(campaign_id), &campaign);
+    /// Get the arbiter list
+    pub fn get_arbiters(env: Env) -> Vec<Address> {
+        env.storage().instance()
+            .get(&DataKey::Arbiters)
+            .unwrap_or(Vec::new(&env))
+    }
 
-        // Record donation
-        let donation = Donation {
-            donor: donor.clone(),
-            amount,
-            timestamp: env.ledger().timestamp(),
-        };
+    /// Approve a verifier allowed to issue attestations (admin only)
+    pub fn add_verifier(env: Env, verifier: Address) {
+        require_admin(&env);
 
-        let mut donations: Vec<Donation> = env.storage().instance()
-            .get(&DataKey::Donations(campaign_id))
-            .unwrap_or(Vec::new(&env));
-        donations.push_back(donation);
-        env.storage().instance().set(&DataKey::Donations(campaign_id), &donations);
+        let mut verifiers = Self::get_verifiers(env.clone());
+        if verifiers.contains(&verifier) {
+            panic!("Already a verifier");
+        }
+        verifiers.push_back(verifier.clone());
+        env.storage().instance().set(&DataKey::Verifiers, &verifiers);
+        env.storage().instance().extend_ttl(100_000, 100_000);
 
-        // Track wallet donations
-        let mut wallet_donations: Vec<u64> = env.storage().instance()
-            .get(&DataKey::WalletDonations(donor.clone()))
-            .unwrap_or(Vec::new(&env));
-        wallet_donations.push_back(campaign_id);
-        env.storage().instance().set(&DataKey::WalletDonations(donor.clone()), &wallet_donations);
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("verifier")),
+            (verifier, true),
+        );
+    }
 
+    /// Remove a verifier (admin only)
+    /// Attestations it issued stop counting while it is not a verifier
+    pub fn remove_verifier(env: Env, verifier: Address) {
+        require_admin(&env);
+
+        let mut verifiers = Self::get_verifiers(env.clone());
+        let index = verifiers.first_index_of(&verifier).expect("Not a verifier");
+        verifiers.remove(index);
+        env.storage().instance().set(&DataKey::Verifiers, &verifiers);
         env.storage().instance().extend_ttl(100_000, 100_000);
 
-        // Emit DonationMade event
         env.events().publish(
-            (symbol_short!("DONATE"), symbol_short!("received")),
-            (campaign_id, donor.clone(), amount, campaign.total_donated),
+            (symbol_short!("ADMIN"), symbol_short!("verifier")),
+            (verifier, false),
         );
+    }
 
-        // Inter-contract call: Mint SST reward tokens to donor
-        // Reward ratio: 1 XLM donated = 10 SST tokens
-        let reward_token_addr: Option<Address> = env.storage().instance()
-            .get(&DataKey::RewardTokenAddr);
-        
-        if let Some(token_addr) = reward_token_addr {
-            let reward_amount = amount * 10; // 10 SST per 1 XLM equivalent
-            
-            // Call the RewardToken contract's mint function
-            let token_client = RewardTokenClient::new(&env, &token_addr);
-            token_client.mint(&donor, &reward_amount);
-            
-            // Emit TokensMinted event
-            env.events().publish(
-                (symbol_short!("TOKEN"), symbol_short!("minted")),
-                (donor, reward_amount, campaign_id),
-            );
+    /// Get the verifier list
+    pub fn get_verifiers(env: Env) -> Vec<Address> {
+        env.storage().instance()
+            .get(&DataKey::Verifiers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Attest a creator's KYC or a campaign's charity status until `expires_at` (verifiers only)
+    /// Replaces the subject's previous attestation unless another verifier's is still live
+    pub fn attest(env: Env, verifier: Address, subject: AttestationSubject, expires_at: u64) {
+        verifier.require_auth();
+
+        if !Self::get_verifiers(env.clone()).contains(&verifier) {
+            panic!("Only verifiers can attest");
+        }
+
+        if let AttestationSubject::Charity(campaign_id) = &subject {
+            load_campaign(&env, *campaign_id);
+        }
+
+        let now = env.ledger().timestamp();
+        if expires_at <= now {
+            panic!("Attestation already expired");
+        }
+
+        let existing: Option<Attestation> = env.storage().instance()
+            .get(&DataKey::Attestation(subject.clone()));
+        if existing.is_some_and(|existing| existing.verifier != verifier && is_live(&env, &existing)) {
+            panic!("Subject attested by another verifier");
         }
 
-        log!(&env, "Donation {} to campaign {}", amount, campaign_id);
+        let attestation = Attestation {
+            verifier: verifier.clone(),
+            issued_at: now,
+            expires_at,
+            revoked: false,
+        };
+        env.storage().instance().set(&DataKey::Attestation(subject.clone()), &attestation);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ATTEST"), symbol_short!("issued")),
+            (subject, verifier, expires_at),
+        );
     }
 
-    /// Close campaign (creator only)
-    pub fn close_campaign(env: Env, campaign_id: u64, caller: Address) {
+    /// Revoke an attestation (issuing verifier or admin only)
+    pub fn revoke_attestation(env: Env, caller: Address, subject: AttestationSubject) {
         caller.require_auth();
 
-        let mut campaign: Campaign = env.storage().instance()
-            .get(&DataKey::Campaign(campaign_id))
-            .expect("Campaign not found");
+        let key = DataKey::Attestation(subject.clone());
+        let mut attestation: Attestation = env.storage().instance()
+            .get(&key)
+            .expect("Attestation not found");
 
-        if campaign.creator != caller {
-            panic!("Only creator can close campaign");
+        if attestation.verifier != caller && Self::get_admin(env.clone()) != Some(caller.clone()) {
+            panic!("Only the issuing verifier or admin can revoke");
         }
 
-        campaign.status = CampaignStatus::Closed;
-        env.storage().instance().set(&DataKey::Campaign(campaign_id), &campaign);
+        attestation.revoked = true;
+        env.storage().instance().set(&key, &attestation);
+        env.storage().instance().extend_ttl(100_000, 100_000);
 
         env.events().publish(
-            (symbol_short!("CAMPAIGN"), symbol_short!("closed")),
-            campaign_id,
+            (symbol_short!("ATTEST"), symbol_short!("revoked")),
+            (subject, caller),
         );
-
-        env.storage().instance().extend_ttl(100_000, 100_000);
     }
 
-    /// Get campaign by ID
-    pub fn get_campaign(env: Env, campaign_id: u64) -> Option<Campaign> {
-        env.storage().instance().get(&DataKey::Campaign(campaign_id))
+    /// Get the attestation recorded for a subject, including expired or revoked ones
+    pub fn get_attestation(env: Env, subject: AttestationSubject) -> Option<Attestation> {
+        env.storage().instance().get(&DataKey::Attestation(subject))
     }
 
-    /// Get all campaigns
-    pub fn get_all_campaigns(env: Env) -> Vec<Campaign> {
-        let campaign_ids: Vec<u64> = env.storage().instance()
-            .get(&DataKey::CampaignList)
+    /// Set the share of contributions, in basis points, needed to open a dispute (admin only)
+    pub fn set_dispute_threshold(env: Env, threshold_bps: u32) {
+        require_admin(&env);
+
+        if threshold_bps == 0 || threshold_bps > 10_000 {
+            panic!("Invalid dispute threshold");
+        }
+
+        env.storage().instance().set(&DataKey::DisputeThreshold, &threshold_bps);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("dispute")),
+            threshold_bps,
+        );
+    }
+
+    /// Get the dispute threshold in basis points
+    pub fn get_dispute_threshold(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&DataKey::DisputeThreshold)
+            .unwrap_or(DEFAULT_DISPUTE_THRESHOLD_BPS)
+    }
+
+    /// Pause or resume new campaigns and donations (admin only)
+    /// Withdrawals and refunds stay available while paused
+    pub fn set_paused(env: Env, paused: bool) {
+        require_admin(&env);
+        env.storage().instance().set(&DataKey::Paused, &paused);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("paused")),
+            paused,
+        );
+    }
+
+    /// Check whether the platform is paused
+    pub fn is_paused(env: Env) -> bool {
+        env.storage().instance().get(&DataKey::Paused).unwrap_or(false)
+    }
+
+    /// Add a curator allowed to feature campaigns (admin only)
+    pub fn add_curator(env: Env, curator: Address) {
+        require_admin(&env);
+
+        let mut curators = Self::get_curators(env.clone());
+        if curators.contains(&curator) {
+            panic!("Already a curator");
+        }
+        curators.push_back(curator.clone());
+        env.storage().instance().set(&DataKey::Curators, &curators);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("curator")),
+            (curator, true),
+        );
+    }
+
+    /// Remove a curator (admin only)
+    pub fn remove_curator(env: Env, curator: Address) {
+        require_admin(&env);
+
+        let mut curators = Self::get_curators(env.clone());
+        let index = curators.first_index_of(&curator).expect("Not a curator");
+        curators.remove(index);
+        env.storage().instance().set(&DataKey::Curators, &curators);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("curator")),
+            (curator, false),
+        );
+    }
+
+    /// Get the curator list
+    pub fn get_curators(env: Env) -> Vec<Address> {
+        env.storage().instance()
+            .get(&DataKey::Curators)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Feature or unfeature a campaign (curator only)
+    pub fn set_featured(env: Env, curator: Address, campaign_id: u64, featured: bool) {
+        curator.require_auth();
+
+        if !Self::get_curators(env.clone()).contains(&curator) {
+            panic!("Only curators can feature campaigns");
+        }
+
+        load_campaign(&env, campaign_id);
+
+        let mut featured_ids = Self::get_featured_campaigns(env.clone());
+        let position = featured_ids.first_index_of(campaign_id);
+        match (featured, position) {
+            (true, None) => featured_ids.push_back(campaign_id),
+            (false, Some(index)) => {
+                featured_ids.remove(index);
+            }
+            _ => return,
+        }
+        env.storage().instance().set(&DataKey::FeaturedCampaigns, &featured_ids);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("CAMPAIGN"), symbol_short!("featured")),
+            (campaign_id, curator, featured),
+        );
+    }
+
+    /// Get featured campaign IDs
+    pub fn get_featured_campaigns(env: Env) -> Vec<u64> {
+        env.storage().instance()
+            .get(&DataKey::FeaturedCampaigns)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Set the token contract used by `donate` (admin only)
+    /// When unset, donations are recorded without moving funds
+    pub fn set_native_asset(env: Env, asset: Address) {
+        require_admin(&env);
+        env.storage().instance().set(&DataKey::NativeAsset, &asset);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+    }
+
+    /// Get the native asset token contract
+    pub fn get_native_asset(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::NativeAsset)
+    }
+
+    /// Configure the SEP-40 price oracle (admin only)
+    pub fn set_price_oracle(env: Env, oracle: Address, max_age: u64) {
+        require_admin(&env);
+
+        if max_age == 0 {
+            panic!("Max price age must be positive");
+        }
+
+        let config = OracleConfig { oracle: oracle.clone(), max_age };
+        env.storage().instance().set(&DataKey::PriceOracle, &config);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("oracle")),
+            (oracle, max_age),
+        );
+    }
+
+    /// Get the price oracle configuration
+    pub fn get_price_oracle(env: Env) -> Option<OracleConfig> {
+        env.storage().instance().get(&DataKey::PriceOracle)
+    }
+
+    /// Configure the platform fee and treasury (admin only)
+    pub fn set_platform_fee(env: Env, fee_bps: u32, treasury: Address) {
+        require_admin(&env);
+
+        if fee_bps > MAX_FEE_BPS {
+            panic!("Fee exceeds maximum");
+        }
+
+        let config = FeeConfig { fee_bps, treasury: treasury.clone() };
+        env.storage().instance().set(&DataKey::FeeConfig, &config);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("fee")),
+            (fee_bps, treasury),
+        );
+    }
+
+    /// Set or clear the bond required to create a campaign (admin only)
+    pub fn set_creation_bond(env: Env, config: Option<BondConfig>) {
+        require_admin(&env);
+
+        match &config {
+            Some(config) => {
+                if config.amount <= 0 {
+                    panic!("Bond amount must be positive");
+                }
+                env.storage().instance().set(&DataKey::BondConfig, config);
+            }
+            None => env.storage().instance().remove(&DataKey::BondConfig),
+        }
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ADMIN"), symbol_short!("bond")),
+            config,
+        );
+    }
+
+    /// Get the campaign creation bond, if any
+    pub fn get_creation_bond(env: Env) -> Option<BondConfig> {
+        env.storage().instance().get(&DataKey::BondConfig)
+    }
+
+    /// Slash a campaign's bond to compensate its donors (admin only)
+    pub fn slash_bond(env: Env, campaign_id: u64) {
+        require_admin(&env);
+        slash_bond(&env, campaign_id);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+    }
+
+    /// Get the platform fee configuration
+    pub fn get_fee_config(env: Env) -> Option<FeeConfig> {
+        env.storage().instance().get(&DataKey::FeeConfig)
+    }
+
+    /// Get platform fees accrued in an asset
+    pub fn get_accrued_fees(env: Env, asset: Address) -> i128 {
+        env.storage().instance()
+            .get(&DataKey::AccruedFees(asset))
+            .unwrap_or(0)
+    }
+
+    /// Send accrued fees in an asset to the treasury (admin only)
+    pub fn withdraw_fees(env: Env, asset: Address) -> i128 {
+        require_admin(&env);
+
+        let config: FeeConfig = env.storage().instance()
+            .get(&DataKey::FeeConfig)
+            .expect("Platform fee not configured");
+
+        let accrued: i128 = env.storage().instance()
+            .get(&DataKey::AccruedFees(asset.clone()))
+            .unwrap_or(0);
+        if accrued <= 0 {
+            panic!("No fees to withdraw");
+        }
+
+        env.storage().instance().remove(&DataKey::AccruedFees(asset.clone()));
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        token::Client::new(&env, &asset)
+            .transfer(&env.current_contract_address(), &config.treasury, &accrued);
+
+        env.events().publish(
+            (symbol_short!("FEE"), symbol_short!("withdrawn")),
+            (asset, config.treasury, accrued),
+        );
+
+        accrued
+    }
+
+    /// Check and mark expired campaigns
+    pub fn check_expired(env: Env, campaign_id: u64) -> bool {
+        let mut campaign: Campaign = env.storage().instance()
+            .get(&DataKey::Campaign(campaign_id))
+            .expect("Campaign not found");
+
+        if campaign.status == CampaignStatus::Open && env.ledger().timestamp() > campaign.end_time {
+            expire_campaign(&env, &mut campaign);
+            env.storage().instance().extend_ttl(100_000, 100_000);
+            return true;
+        }
+        false
+    }
+    /// Create a new campaign
+    pub fn create_campaign(
+        env: Env,
+        creator: Address,
+        title: String,
+        target_amount: i128,
+        duration_days: u64,
+    ) -> u64 {
+        creator.require_auth();
+        require_not_paused(&env);
+
+        if target_amount <= 0 {
+            panic!("Target amount must be positive");
+        }
+
+        let campaign_id: u64 = env.storage().instance()
+            .get(&DataKey::CampaignCounter)
+            .unwrap_or(0) + 1;
+        
+        env.storage().instance().set(&DataKey::CampaignCounter, &campaign_id);
+
+        // Anti-spam bond, returned once the campaign ends cleanly
+        let bond_config: Option<BondConfig> = env.storage().instance().get(&DataKey::BondConfig);
+        if let Some(config) = bond_config {
+            token::Client::new(&env, &config.asset)
+                .transfer(&creator, &env.current_contract_address(), &config.amount);
+
+            let bond = CampaignBond { asset: config.asset, amount: config.amount, status: BondStatus::Held };
+            env.storage().instance().set(&DataKey::CampaignBond(campaign_id), &bond);
+
+            env.events().publish(
+                (symbol_short!("BOND"), symbol_short!("posted")),
+                (campaign_id, creator.clone(), bond.amount),
+            );
+        }
+
+        let end_time = env.ledger().timestamp() + (duration_days * 86400);
+
+        let campaign = Campaign {
+            id: campaign_id,
+            creator: creator.clone(),
+            title: title.clone(),
+            target_amount,
+            total_donated: 0,
+            status: CampaignStatus::Open,
+            end_time,
+        };
+
+        env.storage().instance().set(&DataKey::Campaign(campaign_id), &campaign);
+        update_creator_stats(&env, &creator, |stats| stats.campaigns_launched += 1);
+
+        let mut campaign_list: Vec<u64> = env.storage().instance()
+            .get(&DataKey::CampaignList)
             .unwrap_or(Vec::new(&env));
+        campaign_list.push_back(campaign_id);
+        env.storage().instance().set(&DataKey::CampaignList, &campaign_list);
+
+        let donations: Vec<Donation> = Vec::new(&env);
+        env.storage().instance().set(&DataKey::Donations(campaign_id), &donations);
+
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        // Emit CampaignCreated event
+        env.events().publish(
+            (symbol_short!("CAMPAIGN"), symbol_short!("created")),
+            (campaign_id, creator, title, target_amount),
+        );
+
+        log!(&env, "Campaign {} created", campaign_id);
         
-        let mut campaigns = Vec::new(&env);
-        for id in campaign_ids.iter() {
-            if let Some(campaign) = env.storage().instance().get(&DataKey::Campaign(id)) {
-                campaigns.push_back(campaign);
+        campaign_id
+    }
+
+    /// Donate to a campaign in the native asset
+    /// Anonymous donations hide the donor from public queries, the wallet index
+    /// and this contract's events; the SST contract still reports the mint recipient
+    pub fn donate(
+        env: Env,
+        campaign_id: u64,
+        donor: Address,
+        amount: i128,
+        message: Option<String>,
+        anonymous: bool,
+        tier: Option<u32>,
+    ) {
+        donor.require_auth();
+
+        let native_asset: Option<Address> = env.storage().instance()
+            .get(&DataKey::NativeAsset);
+        let details = DonationDetails { message, anonymous, tier, ..Default::default() };
+        record_donation(&env, campaign_id, &donor, native_asset, amount, false, details);
+    }
+
+    /// Donate to a campaign in one of its accepted assets
+    #[allow(clippy::too_many_arguments)]
+    pub fn donate_asset(
+        env: Env,
+        campaign_id: u64,
+        donor: Address,
+        asset: Address,
+        amount: i128,
+        message: Option<String>,
+        anonymous: bool,
+        tier: Option<u32>,
+    ) {
+        donor.require_auth();
+
+        let details = DonationDetails { message, anonymous, tier, ..Default::default() };
+        record_donation(&env, campaign_id, &donor, Some(asset), amount, false, details);
+    }
+
+    /// Donate on behalf of another address in the native asset
+    /// The payer funds the donation; SST and contribution credit go to the beneficiary
+    pub fn donate_for(
+        env: Env,
+        campaign_id: u64,
+        payer: Address,
+        beneficiary: Address,
+        amount: i128,
+        tier: Option<u32>,
+    ) {
+        payer.require_auth();
+
+        let native_asset: Option<Address> = env.storage().instance()
+            .get(&DataKey::NativeAsset);
+        let details = DonationDetails { payer: Some(payer.clone()), tier, ..Default::default() };
+        record_donation(&env, campaign_id, &beneficiary, native_asset, amount, false, details);
+
+        env.events().publish(
+            (symbol_short!("DONATE"), symbol_short!("gift")),
+            (campaign_id, payer, beneficiary, amount),
+        );
+    }
+
+    /// Retract part or all of recent donations while the campaign is open
+    /// Only donations made within the grace period are eligible, most recent first;
+    /// funds are returned to whoever paid them without fees, sponsor matches return to
+    /// their pools and the matching SST is clawed back (vesting first; any shortfall
+    /// from SST already moved becomes debt)
+    pub fn retract_donation(env: Env, campaign_id: u64, donor: Address, amount: i128) {
+        donor.require_auth();
+
+        if amount <= 0 {
+            panic!("Retraction amount must be positive");
+        }
+
+        let mut campaign = load_campaign(&env, campaign_id);
+
+        // Funded campaigns are final: a retraction can never undo the transition
+        if campaign.status != CampaignStatus::Open {
+            panic!("Campaign is not open");
+        }
+
+        // Dispute support is counted from contributions, which must then stay put
+        let dispute: Option<Dispute> = env.storage().instance().get(&DataKey::Dispute(campaign_id));
+        if dispute.is_some_and(|dispute| dispute.supporters.contains(&donor)) {
+            panic!("Dispute supporters cannot retract");
+        }
+
+        let now = env.ledger().timestamp();
+        let mut donations: Vec<Donation> = env.storage().instance()
+            .get(&DataKey::Donations(campaign_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut remaining = amount;
+        let mut match_returned = 0i128;
+        let mut reward_amount = 0i128;
+        let mut removed = 0u32;
+        let mut anonymous = false;
+        let mut index = donations.len();
+        while index > 0 && remaining > 0 {
+            index -= 1;
+            let mut donation = donations.get(index).unwrap();
+            if donation.donor != donor || now > donation.timestamp + RETRACT_GRACE_PERIOD {
+                continue;
+            }
+            anonymous |= donation.anonymous;
+
+            let taken = remaining.min(donation.value);
+            let refund = donation.amount * taken / donation.value;
+            let burned = donation.reward * taken / donation.value;
+            let unmatched = donation.match_value * taken / donation.value;
+            remaining -= taken;
+            match_returned += unmatched;
+            reward_amount += burned;
+
+            if let Some(asset) = &donation.asset {
+                let raised_key = DataKey::Raised(campaign_id, asset.clone());
+                let raised: i128 = env.storage().instance().get(&raised_key).unwrap_or(0);
+                env.storage().instance().set(&raised_key, &(raised - refund));
+
+                if refund > 0 {
+                    token::Client::new(&env, asset)
+                        .transfer(&env.current_contract_address(), &donation.payer, &refund);
+                }
+
+                // The sponsor match of the retracted share goes back to its pools
+                for (pool_id, matched) in donation.matches.iter() {
+                    let returned = matched * taken / donation.value;
+                    return_match(&env, campaign_id, asset, pool_id, returned);
+                    donation.matches.set(pool_id, matched - returned);
+                }
+            }
+
+            if taken == donation.value {
+                if let Some(tier_id) = donation.tier {
+                    release_tier(&env, campaign_id, tier_id);
+                }
+                donations.remove(index);
+                if !donation.anonymous {
+                    removed += 1;
+                }
+            } else {
+                donation.value -= taken;
+                donation.amount -= refund;
+                donation.reward -= burned;
+                donation.match_value -= unmatched;
+
+                // A partial retraction below the tier minimum gives up the perk
+                if let Some(tier_id) = donation.tier {
+                    let tier = Self::get_reward_tiers(env.clone(), campaign_id).get(tier_id).unwrap();
+                    if donation.value < tier.min_amount {
+                        release_tier(&env, campaign_id, tier_id);
+                        donation.tier = None;
+                    }
+                }
+                donations.set(index, donation);
             }
         }
-        campaigns
+
+        if remaining > 0 {
+            panic!("Retraction exceeds eligible donations");
+        }
+
+        campaign.total_donated -= amount + match_returned;
+        env.storage().instance().set(&DataKey::Campaign(campaign_id), &campaign);
+        env.storage().instance().set(&DataKey::Donations(campaign_id), &donations);
+
+        // Drop one wallet entry per fully retracted public donation
+        let mut wallet_donations: Vec<u64> = env.storage().instance()
+            .get(&DataKey::WalletDonations(donor.clone()))
+            .unwrap_or(Vec::new(&env));
+        for _ in 0..removed {
+            if let Some(position) = wallet_donations.last_index_of(campaign_id) {
+                wallet_donations.remove(position);
+            }
+        }
+        env.storage().instance().set(&DataKey::WalletDonations(donor.clone()), &wallet_donations);
+
+        reduce_round_contribution(&env, campaign_id, &donor, amount);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        // Retracting an anonymous donation must not reveal who made it
+        let public_donor = if anonymous { env.current_contract_address() } else { donor.clone() };
+
+        env.events().publish(
+            (symbol_short!("DONATE"), symbol_short!("retracted")),
+            (campaign_id, public_donor.clone(), amount, campaign.total_donated),
+        );
+
+        // Inter-contract call: reclaim the SST minted for the retracted amount
+        // Clawback rather than a holder burn, so SST already moved or staked becomes debt
+        let reward_token_addr: Option<Address> = env.storage().instance()
+            .get(&DataKey::RewardTokenAddr);
+
+        if let Some(token_addr) = reward_token_addr.filter(|_| reward_amount > 0) {
+            let clawed = RewardTokenClient::new(&env, &token_addr).clawback(&env.current_contract_address(), &donor, &reward_amount);
+
+            env.events().publish(
+                (symbol_short!("TOKEN"), symbol_short!("clawback")),
+                (public_donor, clawed, reward_amount - clawed, campaign_id),
+            );
+        }
+    }
+
+    /// Refund a donor's contributions to an expired, closed or suspended campaign
+    /// Funds are returned to whoever paid them without fees and the SST minted for them is clawed back;
+    /// sponsor matches of the donations return to their pools.
+    /// Suspended streaming campaigns refund only the unstreamed share.
+    pub fn claim_refund(env: Env, campaign_id: u64, donor: Address) -> i128 {
+        donor.require_auth();
+
+        let mut campaign = load_campaign(&env, campaign_id);
+
+        if campaign.status == CampaignStatus::Open && env.ledger().timestamp() > campaign.end_time {
+            expire_campaign(&env, &mut campaign);
+        }
+
+        if campaign.status != CampaignStatus::Expired
+            && campaign.status != CampaignStatus::Closed
+            && campaign.status != CampaignStatus::Suspended
+        {
+            panic!("Campaign is not refundable");
+        }
+
+        let refunded_key = DataKey::Refunded(campaign_id, donor.clone());
+        if env.storage().instance().has(&refunded_key) {
+            panic!("Refund already claimed");
+        }
+
+        let donations: Vec<Donation> = env.storage().instance()
+            .get(&DataKey::Donations(campaign_id))
+            .unwrap_or(Vec::new(&env));
+
+        // Funded campaigns already counted this value as raised and its perks as owed
+        let counted = campaign.total_donated >= campaign.target_amount;
+
+        let mut refunded_value = 0i128;
+        let mut returned_match_value = 0i128;
+        let mut reward_amount = 0i128;
+        let mut perks_cancelled = 0u32;
+        let mut anonymous = false;
+        for (index, donation) in donations.iter().enumerate() {
+            if donation.donor != donor {
+                continue;
+            }
+            anonymous |= donation.anonymous;
+
+            // Refunded pledges give up their perk; only unshipped ones leave the owed count
+            if let Some(tier_id) = donation.tier {
+                release_tier(&env, campaign_id, tier_id);
+                if counted && Self::get_fulfilment(env.clone(), campaign_id, index as u32) == FulfilmentStatus::Pending {
+                    perks_cancelled += 1;
+                }
+            }
+            refunded_value += unstreamed_share(&env, campaign_id, donation.value);
+            returned_match_value += unstreamed_share(&env, campaign_id, donation.match_value);
+            reward_amount += unstreamed_share(&env, campaign_id, donation.reward);
+
+            if let Some(asset) = &donation.asset {
+                let refund = unstreamed_share(&env, campaign_id, donation.amount);
+                let unmatched: i128 = donation.matches.values().iter()
+                    .map(|matched| unstreamed_share(&env, campaign_id, matched))
+                    .sum();
+                if available_funds(&env, campaign_id, asset) < refund + unmatched {
+                    panic!("Insufficient campaign funds");
+                }
+
+                let raised_key = DataKey::Raised(campaign_id, asset.clone());
+                let raised: i128 = env.storage().instance().get(&raised_key).unwrap_or(0);
+                env.storage().instance().set(&raised_key, &(raised - refund));
+
+                token::Client::new(&env, asset)
+                    .transfer(&env.current_contract_address(), &donation.payer, &refund);
+
+                // Sponsor matches of refunded donations go back to their pools
+                for (pool_id, matched) in donation.matches.iter() {
+                    return_match(&env, campaign_id, asset, pool_id, unstreamed_share(&env, campaign_id, matched));
+                }
+            }
+        }
+
+        if refunded_value == 0 {
+            panic!("No donations to refund");
+        }
+
+        env.storage().instance().set(&refunded_key, &true);
+        update_creator_stats(&env, &campaign.creator, |stats| {
+            stats.refunds_issued += 1;
+            stats.perks_owed -= perks_cancelled;
+            if counted {
+                stats.total_raised -= refunded_value + returned_match_value;
+            }
+        });
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        // Refunds of anonymous donations must not reveal who made them
+        let public_donor = if anonymous { env.current_contract_address() } else { donor.clone() };
+
+        env.events().publish(
+            (symbol_short!("DONATE"), symbol_short!("refunded")),
+            (campaign_id, public_donor.clone(), refunded_value),
+        );
+
+        // Inter-contract call: reclaim the SST minted for the refunded donations
+        let reward_token_addr: Option<Address> = env.storage().instance()
+            .get(&DataKey::RewardTokenAddr);
+
+        if let Some(token_addr) = reward_token_addr.filter(|_| reward_amount > 0) {
+            let clawed = RewardTokenClient::new(&env, &token_addr).clawback(&env.current_contract_address(), &donor, &reward_amount);
+
+            env.events().publish(
+                (symbol_short!("TOKEN"), symbol_short!("clawback")),
+                (public_donor, clawed, reward_amount - clawed, campaign_id),
+            );
+        }
+
+        refunded_value
+    }
+
+    /// Check whether a donor has claimed their refund
+    pub fn is_refunded(env: Env, campaign_id: u64, donor: Address) -> bool {
+        env.storage().instance().has(&DataKey::Refunded(campaign_id, donor))
+    }
+
+    /// Accept multiple assets, valued through the price oracle (creator only)
+    /// The target is then measured in the oracle's base unit
+    pub fn set_accepted_assets(env: Env, campaign_id: u64, caller: Address, assets: Vec<Address>) {
+        caller.require_auth();
+
+        let campaign = load_campaign(&env, campaign_id);
+
+        if campaign.creator != caller {
+            panic!("Only creator can set accepted assets");
+        }
+
+        if campaign.status != CampaignStatus::Open || campaign.total_donated > 0 {
+            panic!("Assets can only be set before the first donation");
+        }
+
+        if assets.is_empty() {
+            panic!("At least one asset is required");
+        }
+
+        if !env.storage().instance().has(&DataKey::PriceOracle) {
+            panic!("Price oracle not configured");
+        }
+
+        env.storage().instance().set(&DataKey::CampaignAssets(campaign_id), &assets);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("CAMPAIGN"), symbol_short!("assets")),
+            (campaign_id, assets),
+        );
+    }
+
+    /// Add a pledge tier to an open campaign (creator only)
+    /// Returns the new tier id
+    pub fn add_reward_tier(
+        env: Env,
+        campaign_id: u64,
+        caller: Address,
+        min_amount: i128,
+        description_hash: BytesN<32>,
+        quantity: u32,
+    ) -> u32 {
+        caller.require_auth();
+
+        let campaign = load_campaign(&env, campaign_id);
+
+        if campaign.creator != caller {
+            panic!("Only creator can add reward tiers");
+        }
+
+        if campaign.status != CampaignStatus::Open {
+            panic!("Campaign is not open");
+        }
+
+        if min_amount <= 0 {
+            panic!("Tier minimum must be positive");
+        }
+
+        let mut tiers = Self::get_reward_tiers(env.clone(), campaign_id);
+        let id = tiers.len();
+        tiers.push_back(RewardTier {
+            id,
+            min_amount,
+            description_hash,
+            quantity,
+            claimed: 0,
+        });
+        env.storage().instance().set(&DataKey::RewardTiers(campaign_id), &tiers);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("CAMPAIGN"), symbol_short!("tier")),
+            (campaign_id, id, min_amount, quantity),
+        );
+
+        id
+    }
+
+    /// Get a campaign's pledge tiers
+    pub fn get_reward_tiers(env: Env, campaign_id: u64) -> Vec<RewardTier> {
+        env.storage().instance()
+            .get(&DataKey::RewardTiers(campaign_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the donations that selected a pledge tier, for fulfilment
+    /// Anonymous donors are masked as in `get_donations_by_campaign`
+    pub fn get_tier_donations(env: Env, campaign_id: u64, tier_id: u32) -> Vec<Donation> {
+        let mut tier_donations = Vec::new(&env);
+        for donation in Self::get_donations_by_campaign(env.clone(), campaign_id).iter() {
+            if donation.tier == Some(tier_id) {
+                tier_donations.push_back(donation);
+            }
+        }
+        tier_donations
+    }
+
+    /// Mark a funded donation's perk as shipped (creator only)
+    /// Also used to re-ship after a dispute
+    pub fn mark_shipped(env: Env, campaign_id: u64, caller: Address, donation_index: u32) {
+        caller.require_auth();
+
+        let campaign = load_campaign(&env, campaign_id);
+        if campaign.creator != caller {
+            panic!("Only creator can mark perks shipped");
+        }
+
+        load_perk_donation(&env, &campaign, donation_index);
+        let status = Self::get_fulfilment(env.clone(), campaign_id, donation_index);
+        if status != FulfilmentStatus::Pending && status != FulfilmentStatus::Disputed {
+            panic!("Invalid fulfilment transition");
+        }
+
+        set_fulfilment(&env, &campaign, donation_index, status, FulfilmentStatus::Shipped);
+    }
+
+    /// Confirm receipt of a shipped perk (donor only)
+    /// A disputed perk can also be confirmed once resolved
+    pub fn confirm_fulfilment(env: Env, campaign_id: u64, donor: Address, donation_index: u32) {
+        donor.require_auth();
+
+        let campaign = load_campaign(&env, campaign_id);
+        if load_perk_donation(&env, &campaign, donation_index).donor != donor {
+            panic!("Only the donor can confirm fulfilment");
+        }
+
+        let status = Self::get_fulfilment(env.clone(), campaign_id, donation_index);
+        if status != FulfilmentStatus::Shipped && status != FulfilmentStatus::Disputed {
+            panic!("Invalid fulfilment transition");
+        }
+
+        set_fulfilment(&env, &campaign, donation_index, status, FulfilmentStatus::Confirmed);
+    }
+
+    /// Dispute a pending or shipped perk (donor only)
+    pub fn dispute_fulfilment(env: Env, campaign_id: u64, donor: Address, donation_index: u32) {
+        donor.require_auth();
+
+        let campaign = load_campaign(&env, campaign_id);
+        if load_perk_donation(&env, &campaign, donation_index).donor != donor {
+            panic!("Only the donor can dispute fulfilment");
+        }
+
+        let status = Self::get_fulfilment(env.clone(), campaign_id, donation_index);
+        if status != FulfilmentStatus::Pending && status != FulfilmentStatus::Shipped {
+            panic!("Invalid fulfilment transition");
+        }
+
+        set_fulfilment(&env, &campaign, donation_index, status, FulfilmentStatus::Disputed);
+    }
+
+    /// Get the fulfilment state of a donation's perk
+    pub fn get_fulfilment(env: Env, campaign_id: u64, donation_index: u32) -> FulfilmentStatus {
+        env.storage().instance()
+            .get(&DataKey::Fulfilment(campaign_id, donation_index))
+            .unwrap_or(FulfilmentStatus::Pending)
+    }
+
+    /// Get a creator's aggregated track record
+    pub fn get_creator_stats(env: Env, creator: Address) -> CreatorStats {
+        env.storage().instance()
+            .get(&DataKey::CreatorStats(creator))
+            .unwrap_or_default()
+    }
+
+    /// Replace a campaign's team and payout shares
+    /// Shares are in basis points and must total 10000; the creator must stay on the team.
+    /// While the campaign is open every current and incoming member must consent.
+    /// Addresses that donated to or paid for a donation to the campaign cannot join.
+    pub fn set_team(env: Env, campaign_id: u64, members: Vec<TeamMember>) {
+        let campaign = load_campaign(&env, campaign_id);
+
+        if campaign.status != CampaignStatus::Open {
+            panic!("Campaign is not open");
+        }
+
+        let mut consenting: Vec<Address> = Vec::new(&env);
+        let mut total_bps = 0u32;
+        for member in members.iter() {
+            if member.share_bps == 0 {
+                panic!("Team share must be positive");
+            }
+            if consenting.contains(&member.member) {
+                panic!("Duplicate team member");
+            }
+            total_bps += member.share_bps;
+            consenting.push_back(member.member);
+        }
+
+        if total_bps != 10_000 {
+            panic!("Team shares must total 10000 bps");
+        }
+
+        if !consenting.contains(&campaign.creator) {
+            panic!("Creator must remain on the team");
+        }
+
+        // Payees cannot also hold donations they could retract, refund or dispute
+        let donations: Vec<Donation> = env.storage().instance()
+            .get(&DataKey::Donations(campaign_id))
+            .unwrap_or(Vec::new(&env));
+        if donations.iter().any(|donation| {
+            consenting.contains(&donation.donor) || consenting.contains(&donation.payer)
+        }) {
+            panic!("Donors cannot join the team");
+        }
+
+        for member in campaign_team(&env, &campaign).iter() {
+            if !consenting.contains(&member.member) {
+                consenting.push_back(member.member);
+            }
+        }
+        for member in consenting.iter() {
+            member.require_auth();
+        }
+
+        env.storage().instance().set(&DataKey::CampaignTeam(campaign_id), &members);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("CAMPAIGN"), symbol_short!("team")),
+            (campaign_id, members),
+        );
+    }
+
+    /// Get a campaign's team and payout shares
+    pub fn get_team(env: Env, campaign_id: u64) -> Vec<TeamMember> {
+        campaign_team(&env, &load_campaign(&env, campaign_id))
+    }
+
+    /// Get the assets a campaign accepts
+    pub fn get_accepted_assets(env: Env, campaign_id: u64) -> Vec<Address> {
+        accepted_assets(&env, campaign_id)
+    }
+
+    /// Get the raw amount of an asset raised by a campaign
+    pub fn get_raised_by_asset(env: Env, campaign_id: u64, asset: Address) -> i128 {
+        env.storage().instance()
+            .get(&DataKey::Raised(campaign_id, asset))
+            .unwrap_or(0)
+    }
+
+    /// Pledge a recurring donation paid through a token allowance
+    /// The donor must approve this contract as spender of `asset`
+    pub fn subscribe(
+        env: Env,
+        campaign_id: u64,
+        donor: Address,
+        asset: Address,
+        amount: i128,
+        interval: u64,
+    ) -> u64 {
+        donor.require_auth();
+        require_not_paused(&env);
+
+        if amount <= 0 {
+            panic!("Subscription amount must be positive");
+        }
+
+        if interval == 0 {
+            panic!("Subscription interval must be positive");
+        }
+
+        let campaign = load_campaign(&env, campaign_id);
+
+        if is_team_member(&env, &campaign, &donor) {
+            panic!("Creator cannot donate to their own campaign");
+        }
+
+        if !may_donate(&env, campaign_id, &donor) {
+            panic!("Donor not allowlisted");
+        }
+
+        if campaign.status != CampaignStatus::Open {
+            panic!("Campaign is not accepting donations");
+        }
+
+        if !accepted_assets(&env, campaign_id).contains(&asset) {
+            panic!("Asset not accepted by campaign");
+        }
+
+        let subscription_id: u64 = env.storage().instance()
+            .get(&DataKey::SubscriptionCounter)
+            .unwrap_or(0) + 1;
+        env.storage().instance().set(&DataKey::SubscriptionCounter, &subscription_id);
+
+        // First payment is due immediately
+        let subscription = Subscription {
+            id: subscription_id,
+            campaign_id,
+            donor: donor.clone(),
+            asset,
+            amount,
+            interval,
+            next_payment: env.ledger().timestamp(),
+            payments_made: 0,
+            skipped_periods: 0,
+            active: true,
+        };
+        env.storage().instance().set(&DataKey::Subscription(subscription_id), &subscription);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("SUBSCR"), symbol_short!("created")),
+            (subscription_id, campaign_id, donor, amount, interval),
+        );
+
+        subscription_id
+    }
+
+    /// Pull the next subscription payment once due (callable by anyone)
+    /// Periods missed entirely are skipped, not charged retroactively, and the
+    /// charge is capped at what the campaign still needs to reach its target
+    /// Returns false when the subscription ended instead, because the payment
+    /// would be rejected: the campaign is no longer open, donations are paused,
+    /// the donor joined the team or lost allowlist access, the asset is no longer
+    /// accepted, an acknowledgement is missing, or the donor's allowance or
+    /// balance cannot cover the charge
+    pub fn process_subscription(env: Env, subscription_id: u64) -> bool {
+        let mut subscription: Subscription = env.storage().instance()
+            .get(&DataKey::Subscription(subscription_id))
+            .expect("Subscription not found");
+
+        if !subscription.active {
+            panic!("Subscription is not active");
+        }
+
+        let now = env.ledger().timestamp();
+        if now < subscription.next_payment {
+            panic!("Subscription payment not due");
+        }
+
+        // A payment that would be rejected ends the subscription rather than failing every retry
+        let campaign = load_campaign(&env, subscription.campaign_id);
+        let donor = subscription.donor.clone();
+        if campaign.status != CampaignStatus::Open
+            || now > campaign.end_time
+            || Self::is_paused(env.clone())
+            || is_team_member(&env, &campaign, &donor)
+            || !may_donate(&env, campaign.id, &donor)
+            || !accepted_assets(&env, campaign.id).contains(&subscription.asset)
+        {
+            end_subscription(&env, &mut subscription);
+            return false;
+        }
+
+        // Never pull more than the remaining target; multi-asset campaigns
+        // convert the remaining value back into the subscription's asset
+        let remaining = campaign.target_amount - campaign.total_donated;
+        let multi_asset = env.storage().instance().has(&DataKey::CampaignAssets(subscription.campaign_id));
+        let mut charge = subscription.amount;
+        if multi_asset {
+            let value = quote_value(&env, &subscription.asset, charge);
+            if value > remaining {
+                charge = (charge * remaining / value).max(1);
+            }
+        } else {
+            charge = charge.min(remaining);
+        }
+        let value = if multi_asset { quote_value(&env, &subscription.asset, charge) } else { charge };
+
+        let token = token::Client::new(&env, &subscription.asset);
+        let allowance = token.allowance(&donor, &env.current_contract_address());
+        if allowance < charge
+            || token.balance(&donor) < charge
+            || !has_acknowledged(&env, &campaign, &donor, value)
+        {
+            end_subscription(&env, &mut subscription);
+            return false;
+        }
+
+        let skipped = (now - subscription.next_payment) / subscription.interval;
+        subscription.next_payment += (skipped + 1) * subscription.interval;
+        subscription.payments_made += 1;
+        subscription.skipped_periods += skipped as u32;
+        env.storage().instance().set(&DataKey::Subscription(subscription_id), &subscription);
+
+        record_donation(
+            &env,
+            subscription.campaign_id,
+            &subscription.donor,
+            Some(subscription.asset.clone()),
+            charge,
+            true,
+            DonationDetails::default(),
+        );
+
+        env.events().publish(
+            (symbol_short!("SUBSCR"), symbol_short!("paid")),
+            (subscription_id, subscription.campaign_id, charge, skipped),
+        );
+
+        // The payment that funds the campaign is the last one
+        if load_campaign(&env, subscription.campaign_id).status != CampaignStatus::Open {
+            end_subscription(&env, &mut subscription);
+        }
+
+        true
+    }
+
+    /// Cancel a subscription (donor only)
+    pub fn cancel_subscription(env: Env, subscription_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let mut subscription: Subscription = env.storage().instance()
+            .get(&DataKey::Subscription(subscription_id))
+            .expect("Subscription not found");
+
+        if subscription.donor != caller {
+            panic!("Only donor can cancel subscription");
+        }
+
+        if !subscription.active {
+            panic!("Subscription is not active");
+        }
+
+        subscription.active = false;
+        env.storage().instance().set(&DataKey::Subscription(subscription_id), &subscription);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("SUBSCR"), symbol_short!("cancelled")),
+            (subscription_id, subscription.campaign_id),
+        );
+    }
+
+    /// Get subscription by ID
+    pub fn get_subscription(env: Env, subscription_id: u64) -> Option<Subscription> {
+        env.storage().instance().get(&DataKey::Subscription(subscription_id))
+    }
+
+    /// Create a matching pool funded by the sponsor
+    /// Donations in `asset` to eligible campaigns are matched at `ratio_bps` until `cap` is used
+    pub fn create_matching_pool(
+        env: Env,
+        sponsor: Address,
+        asset: Address,
+        cap: i128,
+        ratio_bps: u32,
+        campaign_ids: Vec<u64>,
+        expires_at: u64,
+    ) -> u64 {
+        sponsor.require_auth();
+
+        if cap <= 0 {
+            panic!("Pool cap must be positive");
+        }
+
+        if ratio_bps == 0 {
+            panic!("Match ratio must be positive");
+        }
+
+        if expires_at <= env.ledger().timestamp() {
+            panic!("Pool expiry must be in the future");
+        }
+
+        token::Client::new(&env, &asset).transfer(&sponsor, &env.current_contract_address(), &cap);
+
+        let pool_id: u64 = env.storage().instance()
+            .get(&DataKey::MatchingPoolCounter)
+            .unwrap_or(0) + 1;
+        env.storage().instance().set(&DataKey::MatchingPoolCounter, &pool_id);
+
+        let pool = MatchingPool {
+            id: pool_id,
+            sponsor: sponsor.clone(),
+            asset: asset.clone(),
+            campaign_ids: Vec::new(&env),
+            ratio_bps,
+            cap,
+            matched: 0,
+            expires_at,
+            reclaimed: false,
+        };
+        env.storage().instance().set(&DataKey::MatchingPool(pool_id), &pool);
+        set_pool_campaigns(&env, pool_id, campaign_ids);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("MATCH"), symbol_short!("created")),
+            (pool_id, sponsor, asset, cap, ratio_bps),
+        );
+
+        pool_id
+    }
+
+    /// Replace the campaigns eligible for a matching pool (sponsor only)
+    pub fn set_pool_campaigns(env: Env, pool_id: u64, sponsor: Address, campaign_ids: Vec<u64>) {
+        sponsor.require_auth();
+
+        let pool = load_pool(&env, pool_id);
+
+        if pool.sponsor != sponsor {
+            panic!("Only sponsor can configure pool");
+        }
+
+        if env.ledger().timestamp() > pool.expires_at {
+            panic!("Matching pool has expired");
+        }
+
+        set_pool_campaigns(&env, pool_id, campaign_ids);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+    }
+
+    /// Return unused pool funds to the sponsor after expiry
+    pub fn reclaim_pool(env: Env, pool_id: u64, sponsor: Address) -> i128 {
+        sponsor.require_auth();
+
+        let mut pool = load_pool(&env, pool_id);
+
+        if pool.sponsor != sponsor {
+            panic!("Only sponsor can reclaim pool");
+        }
+
+        if env.ledger().timestamp() <= pool.expires_at {
+            panic!("Matching pool has not expired");
+        }
+
+        if pool.reclaimed {
+            panic!("Matching pool already reclaimed");
+        }
+
+        let unused = pool.cap - pool.matched;
+        pool.reclaimed = true;
+        env.storage().instance().set(&DataKey::MatchingPool(pool_id), &pool);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        if unused > 0 {
+            token::Client::new(&env, &pool.asset)
+                .transfer(&env.current_contract_address(), &sponsor, &unused);
+        }
+
+        env.events().publish(
+            (symbol_short!("MATCH"), symbol_short!("reclaimed")),
+            (pool_id, sponsor, unused),
+        );
+
+        unused
+    }
+
+    /// Get matching pool by ID
+    pub fn get_matching_pool(env: Env, pool_id: u64) -> Option<MatchingPool> {
+        env.storage().instance().get(&DataKey::MatchingPool(pool_id))
+    }
+
+    /// Get matching pools that list a campaign as eligible
+    pub fn get_campaign_pools(env: Env, campaign_id: u64) -> Vec<u64> {
+        env.storage().instance()
+            .get(&DataKey::CampaignPools(campaign_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Create a quadratic funding round funded by the sponsor
+    pub fn create_qf_round(
+        env: Env,
+        sponsor: Address,
+        asset: Address,
+        pool: i128,
+        start_time: u64,
+        end_time: u64,
+        campaign_ids: Vec<u64>,
+    ) -> u64 {
+        sponsor.require_auth();
+
+        if pool <= 0 {
+            panic!("Round pool must be positive");
+        }
+
+        if end_time <= start_time || end_time <= env.ledger().timestamp() {
+            panic!("Invalid round window");
+        }
+
+        token::Client::new(&env, &asset).transfer(&sponsor, &env.current_contract_address(), &pool);
+
+        let round_id: u64 = env.storage().instance()
+            .get(&DataKey::QfRoundCounter)
+            .unwrap_or(0) + 1;
+        env.storage().instance().set(&DataKey::QfRoundCounter, &round_id);
+
+        let round = QfRound {
+            id: round_id,
+            sponsor: sponsor.clone(),
+            asset,
+            pool,
+            start_time,
+            end_time,
+            campaign_ids: Vec::new(&env),
+            finalized: false,
+        };
+        env.storage().instance().set(&DataKey::QfRound(round_id), &round);
+
+        for campaign_id in campaign_ids.iter() {
+            add_round_campaign(&env, round_id, campaign_id);
+        }
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("QF"), symbol_short!("created")),
+            (round_id, sponsor, pool, start_time, end_time),
+        );
+
+        round_id
+    }
+
+    /// Make a campaign eligible for a round before it starts (sponsor only)
+    pub fn add_qf_campaign(env: Env, round_id: u64, sponsor: Address, campaign_id: u64) {
+        sponsor.require_auth();
+
+        let round = load_round(&env, round_id);
+
+        if round.sponsor != sponsor {
+            panic!("Only sponsor can configure round");
+        }
+
+        if env.ledger().timestamp() >= round.start_time {
+            panic!("Round has already started");
+        }
+
+        add_round_campaign(&env, round_id, campaign_id);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+    }
+
+    /// Distribute the pool after the round ends (callable by anyone)
+    /// Each campaign's subsidy is (sum of sqrt(per-donor contribution))^2 minus the
+    /// contributions themselves; subsidies are scaled down pro-rata if they exceed the pool
+    pub fn finalize_qf_round(env: Env, round_id: u64) {
+        let mut round = load_round(&env, round_id);
+
+        if env.ledger().timestamp() <= round.end_time {
+            panic!("Round has not ended");
+        }
+
+        if round.finalized {
+            panic!("Round already finalized");
+        }
+
+        let mut weights: Vec<i128> = Vec::new(&env);
+        let mut total_weight = 0i128;
+        for campaign_id in round.campaign_ids.iter() {
+            let donors: Vec<Address> = env.storage().instance()
+                .get(&DataKey::RoundDonors(round_id, campaign_id))
+                .unwrap_or(Vec::new(&env));
+
+            let mut sqrt_sum = 0i128;
+            let mut contributed = 0i128;
+            for donor in donors.iter() {
+                let contribution: i128 = env.storage().instance()
+                    .get(&DataKey::RoundContribution(round_id, campaign_id, donor))
+                    .unwrap_or(0);
+                sqrt_sum += isqrt(contribution);
+                contributed += contribution;
+            }
+
+            // Floored square roots can leave a lone donor's subsidy slightly negative
+            let weight = (sqrt_sum.checked_mul(sqrt_sum).expect("QF weight overflow") - contributed).max(0);
+            total_weight = total_weight.checked_add(weight).expect("QF weight overflow");
+            weights.push_back(weight);
+        }
+
+        let mut distributed = 0i128;
+        if total_weight > 0 {
+            for (index, campaign_id) in round.campaign_ids.iter().enumerate() {
+                let weight = weights.get(index as u32).unwrap();
+                let matched = if total_weight <= round.pool {
+                    weight
+                } else {
+                    round.pool
+                        .checked_mul(weight)
+                        .expect("QF match overflow")
+                        / total_weight
+                };
+                if matched > 0 {
+                    env.storage().instance().set(&DataKey::RoundMatch(round_id, campaign_id), &matched);
+                    distributed += matched;
+                }
+            }
+        }
+
+        round.finalized = true;
+        env.storage().instance().set(&DataKey::QfRound(round_id), &round);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        // Rounding dust and unused pools go back to the sponsor
+        let leftover = round.pool - distributed;
+        if leftover > 0 {
+            token::Client::new(&env, &round.asset)
+                .transfer(&env.current_contract_address(), &round.sponsor, &leftover);
+        }
+
+        env.events().publish(
+            (symbol_short!("QF"), symbol_short!("final")),
+            (round_id, distributed, leftover),
+        );
+    }
+
+    /// Claim a funded campaign's quadratic funding match (team members only)
+    /// The match is credited to the campaign's raised funds, so it is paid out
+    /// through the same withdrawal, multisig or stream path as donations
+    pub fn claim_qf_match(env: Env, round_id: u64, campaign_id: u64, caller: Address) -> i128 {
+        caller.require_auth();
+
+        let round = load_round(&env, round_id);
+        let campaign = load_campaign(&env, campaign_id);
+
+        if !is_team_member(&env, &campaign, &caller) {
+            panic!("Only team members can claim match");
+        }
+
+        if !round.finalized {
+            panic!("Round not finalized");
+        }
+
+        if campaign.status != CampaignStatus::Funded {
+            panic!("Campaign is not funded");
+        }
+
+        let matched: i128 = env.storage().instance()
+            .get(&DataKey::RoundMatch(round_id, campaign_id))
+            .unwrap_or(0);
+        if matched <= 0 {
+            panic!("No match to claim");
+        }
+
+        env.storage().instance().remove(&DataKey::RoundMatch(round_id, campaign_id));
+        credit_raised(&env, campaign_id, &round.asset, matched);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("QF"), symbol_short!("claimed")),
+            (round_id, campaign_id, caller, matched),
+        );
+
+        matched
+    }
+
+    /// Return a campaign's unclaimed match to the sponsor once the campaign
+    /// expired, closed or was suspended (sponsor only)
+    pub fn reclaim_qf_match(env: Env, round_id: u64, campaign_id: u64, sponsor: Address) -> i128 {
+        sponsor.require_auth();
+
+        let round = load_round(&env, round_id);
+        if round.sponsor != sponsor {
+            panic!("Only sponsor can reclaim match");
+        }
+
+        let mut campaign = load_campaign(&env, campaign_id);
+        if campaign.status == CampaignStatus::Open && env.ledger().timestamp() > campaign.end_time {
+            expire_campaign(&env, &mut campaign);
+        }
+
+        if campaign.status == CampaignStatus::Open || campaign.status == CampaignStatus::Funded {
+            panic!("Campaign has not failed");
+        }
+
+        let matched: i128 = env.storage().instance()
+            .get(&DataKey::RoundMatch(round_id, campaign_id))
+            .unwrap_or(0);
+        if matched <= 0 {
+            panic!("No match to claim");
+        }
+
+        env.storage().instance().remove(&DataKey::RoundMatch(round_id, campaign_id));
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        token::Client::new(&env, &round.asset)
+            .transfer(&env.current_contract_address(), &sponsor, &matched);
+
+        env.events().publish(
+            (symbol_short!("QF"), symbol_short!("reclaimed")),
+            (round_id, campaign_id, matched),
+        );
+
+        matched
+    }
+
+    /// Get quadratic funding round by ID
+    pub fn get_qf_round(env: Env, round_id: u64) -> Option<QfRound> {
+        env.storage().instance().get(&DataKey::QfRound(round_id))
+    }
+
+    /// Get a donor's total contribution to a campaign within a round
+    pub fn get_round_contribution(env: Env, round_id: u64, campaign_id: u64, donor: Address) -> i128 {
+        env.storage().instance()
+            .get(&DataKey::RoundContribution(round_id, campaign_id, donor))
+            .unwrap_or(0)
+    }
+
+    /// Get a campaign's unclaimed match from a finalized round
+    pub fn get_round_match(env: Env, round_id: u64, campaign_id: u64) -> i128 {
+        env.storage().instance()
+            .get(&DataKey::RoundMatch(round_id, campaign_id))
+            .unwrap_or(0)
+    }
+
+    /// Release a funded campaign's raised assets to its team, net of platform fees
+    pub fn withdraw(env: Env, campaign_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let campaign = load_campaign(&env, campaign_id);
+
+        if !is_team_member(&env, &campaign, &caller) {
+            panic!("Only team members can withdraw");
+        }
+
+        if env.storage().instance().has(&DataKey::WithdrawalSigners(campaign_id)) {
+            panic!("Campaign requires multisig withdrawal");
+        }
+
+        if env.storage().instance().has(&DataKey::PayoutStream(campaign_id)) {
+            panic!("Campaign streams payouts");
+        }
+
+        withdraw_available(&env, &campaign);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+    }
+
+    /// Require M-of-N signer approval for a campaign's withdrawals (creator only)
+    /// Can only be configured while the campaign is open
+    pub fn set_withdrawal_signers(
+        env: Env,
+        campaign_id: u64,
+        caller: Address,
+        signers: Vec<Address>,
+        threshold: u32,
+    ) {
+        caller.require_auth();
+
+        let campaign = load_campaign(&env, campaign_id);
+
+        if campaign.creator != caller {
+            panic!("Only creator can set withdrawal signers");
+        }
+
+        if campaign.status != CampaignStatus::Open {
+            panic!("Campaign is not open");
+        }
+
+        if env.storage().instance().has(&DataKey::PayoutStream(campaign_id)) {
+            panic!("Campaign streams payouts");
+        }
+
+        for (index, signer) in signers.iter().enumerate() {
+            if signers.first_index_of(&signer) != Some(index as u32) {
+                panic!("Duplicate signer");
+            }
+        }
+
+        if threshold == 0 || threshold > signers.len() {
+            panic!("Invalid signer threshold");
+        }
+
+        let config = WithdrawalSigners { signers, threshold };
+        env.storage().instance().set(&DataKey::WithdrawalSigners(campaign_id), &config);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("WITHDRAW"), symbol_short!("signers")),
+            (campaign_id, config.signers, threshold),
+        );
+    }
+
+    /// Get a campaign's withdrawal signer set, if any
+    pub fn get_withdrawal_signers(env: Env, campaign_id: u64) -> Option<WithdrawalSigners> {
+        env.storage().instance().get(&DataKey::WithdrawalSigners(campaign_id))
+    }
+
+    /// Propose withdrawing a funded campaign's available funds (signers only)
+    /// The proposer's approval is counted; the proposal expires after a week
+    pub fn propose_withdrawal(env: Env, campaign_id: u64, signer: Address) -> u64 {
+        signer.require_auth();
+
+        let campaign = load_campaign(&env, campaign_id);
+        require_withdrawal_signer(&env, campaign_id, &signer);
+
+        if campaign.status != CampaignStatus::Funded {
+            panic!("Campaign is not funded");
+        }
+
+        let proposal_id: u64 = env.storage().instance()
+            .get(&DataKey::WithdrawalProposalCounter)
+            .unwrap_or(0) + 1;
+        env.storage().instance().set(&DataKey::WithdrawalProposalCounter, &proposal_id);
+
+        let proposal = WithdrawalProposal {
+            id: proposal_id,
+            campaign_id,
+            proposer: signer.clone(),
+            approvals: Vec::from_array(&env, [signer.clone()]),
+            expires_at: env.ledger().timestamp() + WITHDRAWAL_PROPOSAL_TTL,
+            executed: false,
+        };
+        env.storage().instance().set(&DataKey::WithdrawalProposal(proposal_id), &proposal);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("WITHDRAW"), symbol_short!("proposed")),
+            (proposal_id, campaign_id, signer, proposal.expires_at),
+        );
+
+        proposal_id
+    }
+
+    /// Approve a pending withdrawal proposal (signers only)
+    pub fn approve_withdrawal(env: Env, proposal_id: u64, signer: Address) {
+        signer.require_auth();
+
+        let mut proposal = load_pending_withdrawal(&env, proposal_id);
+        require_withdrawal_signer(&env, proposal.campaign_id, &signer);
+
+        if proposal.approvals.contains(&signer) {
+            panic!("Already approved");
+        }
+
+        proposal.approvals.push_back(signer.clone());
+        env.storage().instance().set(&DataKey::WithdrawalProposal(proposal_id), &proposal);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("WITHDRAW"), symbol_short!("approved")),
+            (proposal_id, signer, proposal.approvals.len()),
+        );
+    }
+
+    /// Execute a withdrawal proposal once it reaches the signer threshold
+    /// Funds are released to the campaign team as with `withdraw`
+    pub fn execute_withdrawal(env: Env, proposal_id: u64, signer: Address) {
+        signer.require_auth();
+
+        let mut proposal = load_pending_withdrawal(&env, proposal_id);
+        let config = require_withdrawal_signer(&env, proposal.campaign_id, &signer);
+
+        // Only approvals from current signers count
+        let approvals = proposal.approvals.iter()
+            .filter(|approver| config.signers.contains(approver))
+            .count() as u32;
+        if approvals < config.threshold {
+            panic!("Not enough approvals");
+        }
+
+        let campaign = load_campaign(&env, proposal.campaign_id);
+        proposal.executed = true;
+        env.storage().instance().set(&DataKey::WithdrawalProposal(proposal_id), &proposal);
+
+        withdraw_available(&env, &campaign);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("WITHDRAW"), symbol_short!("executed")),
+            (proposal_id, proposal.campaign_id, signer),
+        );
+    }
+
+    /// Opt an open campaign into streaming payouts (creator only)
+    /// Once funded, raised funds become claimable linearly over `duration` seconds
+    pub fn set_payout_stream(env: Env, campaign_id: u64, caller: Address, duration: u64) {
+        caller.require_auth();
+
+        let campaign = load_campaign(&env, campaign_id);
+
+        if campaign.creator != caller {
+            panic!("Only creator can set payout stream");
+        }
+
+        if campaign.status != CampaignStatus::Open {
+            panic!("Campaign is not open");
+        }
+
+        if env.storage().instance().has(&DataKey::WithdrawalSigners(campaign_id)) {
+            panic!("Campaign requires multisig withdrawal");
+        }
+
+        if duration == 0 {
+            panic!("Stream duration must be positive");
+        }
+
+        let stream = PayoutStream { duration, started_at: None, stopped_at: None };
+        env.storage().instance().set(&DataKey::PayoutStream(campaign_id), &stream);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("STREAM"), symbol_short!("set")),
+            (campaign_id, duration),
+        );
+    }
+
+    /// Get a campaign's payout stream, if any
+    pub fn get_payout_stream(env: Env, campaign_id: u64) -> Option<PayoutStream> {
+        env.storage().instance().get(&DataKey::PayoutStream(campaign_id))
+    }
+
+    /// Get the streamed funds the team can claim now, per asset
+    pub fn claimable(env: Env, campaign_id: u64) -> Map<Address, i128> {
+        let stream = load_stream(&env, campaign_id);
+
+        let mut claimable = Map::new(&env);
+        for asset in held_assets(&env, campaign_id).iter() {
+            let withdrawn: i128 = env.storage().instance()
+                .get(&DataKey::Withdrawn(campaign_id, asset.clone()))
+                .unwrap_or(0);
+            let amount = streamed_amount(&env, campaign_id, &stream, &asset) - withdrawn;
+            claimable.set(asset, amount.max(0));
+        }
+        claimable
+    }
+
+    /// Release the streamed funds claimable so far to the campaign team
+    /// Anyone may trigger the release; funds only go to team members
+    pub fn claim_stream(env: Env, campaign_id: u64) -> Map<Address, i128> {
+        let campaign = load_campaign(&env, campaign_id);
+
+        if campaign.status != CampaignStatus::Funded && campaign.status != CampaignStatus::Suspended {
+            panic!("Campaign is not funded");
+        }
+
+        let claimable = Self::claimable(env.clone(), campaign_id);
+        let mut released = 0i128;
+        for (asset, amount) in claimable.iter() {
+            if amount <= 0 {
+                continue;
+            }
+
+            let withdrawn_key = DataKey::Withdrawn(campaign_id, asset.clone());
+            let withdrawn: i128 = env.storage().instance().get(&withdrawn_key).unwrap_or(0);
+            env.storage().instance().set(&withdrawn_key, &(withdrawn + amount));
+
+            release_funds(&env, &campaign, &asset, amount);
+            released += amount;
+        }
+
+        if released == 0 {
+            panic!("Nothing to claim");
+        }
+
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("STREAM"), symbol_short!("claimed")),
+            (campaign_id, claimable.clone()),
+        );
+
+        claimable
+    }
+
+    /// Back a dispute against an open or funded campaign (donors only)
+    /// Once supporters hold the threshold share of contributions (or of the
+    /// target while still open), the dispute opens and withdrawals freeze
+    /// until arbiters resolve it or it times out
+    pub fn raise_dispute(env: Env, campaign_id: u64, donor: Address) {
+        donor.require_auth();
+
+        let campaign = load_campaign(&env, campaign_id);
+        if campaign.status != CampaignStatus::Open && campaign.status != CampaignStatus::Funded {
+            panic!("Campaign is not open or funded");
+        }
+
+        // Without arbiters every dispute would simply time out in the donors' favour
+        if Self::get_arbiters(env.clone()).is_empty() {
+            panic!("No arbiters configured");
+        }
+
+        let mut dispute: Dispute = env.storage().instance()
+            .get(&DataKey::Dispute(campaign_id))
+            .unwrap_or(Dispute {
+                campaign_id,
+                supporters: Vec::new(&env),
+                support: 0,
+                status: DisputeStatus::Gathering,
+                deadline: 0,
+            });
+
+        if dispute.status != DisputeStatus::Gathering {
+            panic!("Dispute already raised");
+        }
+
+        if dispute.supporters.contains(&donor) {
+            panic!("Already supporting dispute");
+        }
+
+        let donations: Vec<Donation> = env.storage().instance()
+            .get(&DataKey::Donations(campaign_id))
+            .unwrap_or(Vec::new(&env));
+        let contribution: i128 = donations.iter()
+            .filter(|donation| donation.donor == donor)
+            .map(|donation| donation.value)
+            .sum();
+        if contribution == 0 {
+            panic!("Only donors can raise disputes");
+        }
+
+        dispute.supporters.push_back(donor.clone());
+        dispute.support += contribution;
+
+        env.events().publish(
+            (symbol_short!("DISPUTE"), symbol_short!("support")),
+            (campaign_id, donor, dispute.support),
+        );
+
+        // Measured against the target too, so a few early donors cannot open one alone
+        let base = campaign.total_donated.max(campaign.target_amount);
+        let threshold = Self::get_dispute_threshold(env.clone()) as i128;
+        if dispute.support * 10_000 >= base * threshold {
+            dispute.status = DisputeStatus::Open;
+            dispute.deadline = env.ledger().timestamp() + DISPUTE_TIMEOUT;
+
+            env.events().publish(
+                (symbol_short!("DISPUTE"), symbol_short!("opened")),
+                (campaign_id, dispute.support, dispute.deadline),
+            );
+        }
+
+        env.storage().instance().set(&DataKey::Dispute(campaign_id), &dispute);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+    }
+
+    /// Resolve an open dispute before its deadline (arbiters only)
+    /// `refund` suspends the campaign so donors can reclaim funds pro-rata;
+    /// otherwise withdrawals unfreeze
+    pub fn resolve_dispute(env: Env, campaign_id: u64, arbiter: Address, refund: bool) {
+        arbiter.require_auth();
+
+        if !Self::get_arbiters(env.clone()).contains(&arbiter) {
+            panic!("Only arbiters can resolve disputes");
+        }
+
+        let mut dispute = load_open_dispute(&env, campaign_id);
+        if env.ledger().timestamp() > dispute.deadline {
+            panic!("Dispute timed out");
+        }
+
+        if refund {
+            refund_dispute(&env, &mut dispute);
+        } else {
+            dispute.status = DisputeStatus::Released;
+            env.storage().instance().set(&DataKey::Dispute(campaign_id), &dispute);
+        }
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("DISPUTE"), symbol_short!("resolved")),
+            (campaign_id, arbiter, dispute.status),
+        );
+    }
+
+    /// Settle a dispute the arbiters did not resolve in time
+    /// Unresolved disputes favour donors: the campaign is suspended for refunds
+    pub fn timeout_dispute(env: Env, campaign_id: u64) {
+        let mut dispute = load_open_dispute(&env, campaign_id);
+        if env.ledger().timestamp() <= dispute.deadline {
+            panic!("Dispute has not timed out");
+        }
+
+        refund_dispute(&env, &mut dispute);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("DISPUTE"), symbol_short!("timeout")),
+            campaign_id,
+        );
+    }
+
+    /// Return a campaign's bond after a clean end (creator only)
+    /// Clean ends are a funded campaign with all funds paid out and no open or
+    /// upheld dispute, a campaign that expired undisputed without reaching its target,
+    /// or one the creator closed before receiving any donation
+    pub fn reclaim_bond(env: Env, campaign_id: u64, caller: Address) -> i128 {
+        caller.require_auth();
+
+        let mut campaign = load_campaign(&env, campaign_id);
+        if campaign.creator != caller {
+            panic!("Only creator can reclaim bond");
+        }
+
+        let key = DataKey::CampaignBond(campaign_id);
+        let mut bond: CampaignBond = env.storage().instance().get(&key).expect("No bond posted");
+        if bond.status != BondStatus::Held {
+            panic!("Bond already settled");
+        }
+
+        if campaign.status == CampaignStatus::Open && env.ledger().timestamp() > campaign.end_time {
+            expire_campaign(&env, &mut campaign);
+        }
+
+        let dispute: Option<Dispute> = env.storage().instance().get(&DataKey::Dispute(campaign_id));
+        let disputed = dispute.is_some_and(|dispute| {
+            dispute.status == DisputeStatus::Open || dispute.status == DisputeStatus::Refunded
+        });
+        let paid_out = held_assets(&env, campaign_id).iter()
+            .all(|asset| available_funds(&env, campaign_id, &asset) == 0);
+
+        let donations: Vec<Donation> = env.storage().instance()
+            .get(&DataKey::Donations(campaign_id))
+            .unwrap_or(Vec::new(&env));
+
+        let clean = match campaign.status {
+            CampaignStatus::Funded => paid_out && !disputed,
+            CampaignStatus::Expired => !disputed,
+            CampaignStatus::Closed => donations.is_empty(),
+            _ => false,
+        };
+        if !clean {
+            panic!("Campaign has not ended cleanly");
+        }
+
+        bond.status = BondStatus::Refunded;
+        env.storage().instance().set(&key, &bond);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        token::Client::new(&env, &bond.asset)
+            .transfer(&env.current_contract_address(), &caller, &bond.amount);
+
+        env.events().publish(
+            (symbol_short!("BOND"), symbol_short!("refunded")),
+            (campaign_id, caller, bond.amount),
+        );
+
+        bond.amount
+    }
+
+    /// Claim a donor's pro-rata share of a slashed bond
+    pub fn claim_bond_compensation(env: Env, campaign_id: u64, donor: Address) -> i128 {
+        donor.require_auth();
+
+        let bond: CampaignBond = env.storage().instance()
+            .get(&DataKey::CampaignBond(campaign_id))
+            .expect("No bond posted");
+        if bond.status != BondStatus::Slashed {
+            panic!("Bond not slashed");
+        }
+
+        let claimed_key = DataKey::BondClaimed(campaign_id, donor.clone());
+        if env.storage().instance().has(&claimed_key) {
+            panic!("Compensation already claimed");
+        }
+
+        let donations: Vec<Donation> = env.storage().instance()
+            .get(&DataKey::Donations(campaign_id))
+            .unwrap_or(Vec::new(&env));
+        let mut contribution = 0i128;
+        let mut total = 0i128;
+        for donation in donations.iter() {
+            total += donation.value;
+            if donation.donor == donor {
+                contribution += donation.value;
+            }
+        }
+
+        let share = if total > 0 { bond.amount * contribution / total } else { 0 };
+        if share == 0 {
+            panic!("No compensation owed");
+        }
+
+        env.storage().instance().set(&claimed_key, &true);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        token::Client::new(&env, &bond.asset)
+            .transfer(&env.current_contract_address(), &donor, &share);
+
+        env.events().publish(
+            (symbol_short!("BOND"), symbol_short!("claimed")),
+            (campaign_id, donor, share),
+        );
+
+        share
+    }
+
+    /// Get a campaign's bond, if one was posted
+    pub fn get_campaign_bond(env: Env, campaign_id: u64) -> Option<CampaignBond> {
+        env.storage().instance().get(&DataKey::CampaignBond(campaign_id))
+    }
+
+    /// Get a campaign's dispute, if any
+    pub fn get_dispute(env: Env, campaign_id: u64) -> Option<Dispute> {
+        env.storage().instance().get(&DataKey::Dispute(campaign_id))
+    }
+
+    /// Get a withdrawal proposal
+    pub fn get_withdrawal_proposal(env: Env, proposal_id: u64) -> Option<WithdrawalProposal> {
+        env.storage().instance().get(&DataKey::WithdrawalProposal(proposal_id))
+    }
+
+    /// Get the amount of an asset a campaign has not yet paid out
+    pub fn get_available_funds(env: Env, campaign_id: u64, asset: Address) -> i128 {
+        available_funds(&env, campaign_id, &asset)
+    }
+
+    /// Close an open campaign (creator only); donors can then claim refunds
+    pub fn close_campaign(env: Env, campaign_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let mut campaign: Campaign = env.storage().instance()
+            .get(&DataKey::Campaign(campaign_id))
+            .expect("Campaign not found");
+
+        if campaign.creator != caller {
+            panic!("Only creator can close campaign");
+        }
+
+        // Funded campaigns are final; closing would make them refundable
+        if campaign.status != CampaignStatus::Open {
+            panic!("Campaign is not open");
+        }
+
+        update_creator_stats(&env, &campaign.creator, |stats| stats.campaigns_failed += 1);
+
+        campaign.status = CampaignStatus::Closed;
+        env.storage().instance().set(&DataKey::Campaign(campaign_id), &campaign);
+
+        env.events().publish(
+            (symbol_short!("CAMPAIGN"), symbol_short!("closed")),
+            campaign_id,
+        );
+
+        env.storage().instance().extend_ttl(100_000, 100_000);
+    }
+
+    /// Get campaign by ID
+    pub fn get_campaign(env: Env, campaign_id: u64) -> Option<Campaign> {
+        env.storage().instance().get(&DataKey::Campaign(campaign_id))
+    }
+
+    /// Make a campaign private or public (creator only)
+    /// Private campaigns only accept donations from allowlisted addresses
+    pub fn set_campaign_private(env: Env, campaign_id: u64, caller: Address, private: bool) {
+        load_creator_campaign(&env, campaign_id, &caller);
+
+        env.storage().instance().set(&AllowlistKey::PrivateCampaign(campaign_id), &private);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("CAMPAIGN"), symbol_short!("private")),
+            (campaign_id, private),
+        );
+    }
+
+    /// Check whether a campaign is private
+    pub fn is_campaign_private(env: Env, campaign_id: u64) -> bool {
+        env.storage().instance()
+            .get(&AllowlistKey::PrivateCampaign(campaign_id))
+            .unwrap_or(false)
+    }
+
+    /// Allow addresses to donate to a private campaign (creator only)
+    pub fn add_to_allowlist(env: Env, campaign_id: u64, caller: Address, donors: Vec<Address>) {
+        load_creator_campaign(&env, campaign_id, &caller);
+
+        for donor in donors.iter() {
+            allowlist(&env, campaign_id, donor);
+        }
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ALLOW"), symbol_short!("added")),
+            (campaign_id, donors),
+        );
+    }
+
+    /// Remove addresses from a private campaign's allowlist (creator only)
+    /// Addresses proven against the Merkle root can prove again until the root changes
+    pub fn remove_from_allowlist(env: Env, campaign_id: u64, caller: Address, donors: Vec<Address>) {
+        load_creator_campaign(&env, campaign_id, &caller);
+
+        for donor in donors.iter() {
+            env.storage().persistent().remove(&AllowlistKey::Allowlisted(campaign_id, donor));
+        }
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ALLOW"), symbol_short!("removed")),
+            (campaign_id, donors),
+        );
+    }
+
+    /// Set or clear the Merkle root of a private campaign's allowlist (creator only)
+    /// Leaves are the SHA-256 of each donor address's XDR; pairs are hashed in sorted order
+    pub fn set_allowlist_root(env: Env, campaign_id: u64, caller: Address, root: Option<BytesN<32>>) {
+        load_creator_campaign(&env, campaign_id, &caller);
+
+        match &root {
+            Some(root) => env.storage().instance().set(&AllowlistKey::Root(campaign_id), root),
+            None => env.storage().instance().remove(&AllowlistKey::Root(campaign_id)),
+        }
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ALLOW"), symbol_short!("root")),
+            (campaign_id, root),
+        );
+    }
+
+    /// Add a donor to a private campaign's allowlist with a Merkle proof
+    pub fn prove_allowlist(env: Env, campaign_id: u64, donor: Address, proof: Vec<BytesN<32>>) {
+        let root: BytesN<32> = env.storage().instance()
+            .get(&AllowlistKey::Root(campaign_id))
+            .expect("Campaign has no allowlist root");
+
+        let mut node: BytesN<32> = env.crypto().sha256(&donor.clone().to_xdr(&env)).into();
+        for sibling in proof.iter() {
+            let (first, second) = if node < sibling { (node, sibling) } else { (sibling, node) };
+            let mut pair = Bytes::from(first);
+            pair.append(&Bytes::from(second));
+            node = env.crypto().sha256(&pair).into();
+        }
+
+        if node != root {
+            panic!("Invalid allowlist proof");
+        }
+
+        allowlist(&env, campaign_id, donor.clone());
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ALLOW"), symbol_short!("proven")),
+            (campaign_id, donor),
+        );
+    }
+
+    /// Check whether an address may donate to a private campaign
+    pub fn is_allowlisted(env: Env, campaign_id: u64, donor: Address) -> bool {
+        env.storage().persistent()
+            .get(&AllowlistKey::Allowlisted(campaign_id, donor))
+            .unwrap_or(false)
+    }
+
+    /// Get a campaign's current verification status
+    pub fn get_campaign_verification(env: Env, campaign_id: u64) -> Verification {
+        campaign_verification(&env, &load_campaign(&env, campaign_id))
+    }
+
+    /// Require donors to acknowledge the verification status before donating
+    /// `threshold` or more (creator only); 0 disables the requirement
+    pub fn set_ack_threshold(env: Env, campaign_id: u64, caller: Address, threshold: i128) {
+        caller.require_auth();
+
+        let campaign = load_campaign(&env, campaign_id);
+        if campaign.creator != caller {
+            panic!("Only creator can set acknowledgement threshold");
+        }
+
+        if threshold < 0 {
+            panic!("Threshold cannot be negative");
+        }
+
+        env.storage().instance().set(&DataKey::AckThreshold(campaign_id), &threshold);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("CAMPAIGN"), symbol_short!("ackthresh")),
+            (campaign_id, threshold),
+        );
+    }
+
+    /// Record that a donor has seen a campaign's current verification status
+    pub fn acknowledge_verification(env: Env, campaign_id: u64, donor: Address) -> Verification {
+        donor.require_auth();
+
+        let verification = campaign_verification(&env, &load_campaign(&env, campaign_id));
+        env.storage().instance().set(
+            &DataKey::VerificationAck(campaign_id, donor.clone()),
+            &verification,
+        );
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("ATTEST"), symbol_short!("ack")),
+            (campaign_id, donor),
+        );
+
+        verification
+    }
+
+    /// Get all campaigns
+    /// Private campaigns are only listed when `include_private` is set
+    pub fn get_all_campaigns(env: Env, include_private: bool) -> Vec<Campaign> {
+        let campaign_ids: Vec<u64> = env.storage().instance()
+            .get(&DataKey::CampaignList)
+            .unwrap_or(Vec::new(&env));
+        
+        let mut campaigns = Vec::new(&env);
+        for id in campaign_ids.iter() {
+            if !include_private && Self::is_campaign_private(env.clone(), id) {
+                continue;
+            }
+            if let Some(campaign) = env.storage().instance().get(&DataKey::Campaign(id)) {
+                campaigns.push_back(campaign);
+            }
+        }
+        campaigns
+    }
+
+    /// Get campaigns by creator, including private ones
+    pub fn get_campaigns_by_creator(env: Env, creator: Address) -> Vec<Campaign> {
+        let all_campaigns = Self::get_all_campaigns(env.clone(), true);
+        let mut creator_campaigns = Vec::new(&env);
+        
+        for campaign in all_campaigns.iter() {
+            if campaign.creator == creator {
+                creator_campaigns.push_back(campaign);
+            }
+        }
+        creator_campaigns
+    }
+
+    /// Get donations for a campaign
+    /// Anonymous donations report the contract address as donor and payer
+    pub fn get_donations_by_campaign(env: Env, campaign_id: u64) -> Vec<Donation> {
+        let donations: Vec<Donation> = env.storage().instance()
+            .get(&DataKey::Donations(campaign_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut public_donations = Vec::new(&env);
+        for mut donation in donations.iter() {
+            if donation.anonymous {
+                donation.donor = env.current_contract_address();
+                donation.payer = env.current_contract_address();
+            }
+            public_donations.push_back(donation);
+        }
+        public_donations
+    }
+
+    /// Get campaigns a wallet donated to
+    pub fn get_donations_by_wallet(env: Env, wallet: Address) -> Vec<u64> {
+        env.storage().instance()
+            .get(&DataKey::WalletDonations(wallet))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get campaign count
+    pub fn get_campaign_count(env: Env) -> u64 {
+        env.storage().instance()
+            .get(&DataKey::CampaignCounter)
+            .unwrap_or(0)
+    }
+}
+
+/// Load a campaign or panic
+fn load_campaign(env: &Env, campaign_id: u64) -> Campaign {
+    env.storage().instance()
+        .get(&DataKey::Campaign(campaign_id))
+        .expect("Campaign not found")
+}
+
+/// SST minted per unit donated
+fn reward_rate(env: &Env) -> i128 {
+    env.storage().instance()
+        .get(&DataKey::RewardRate)
+        .unwrap_or(DEFAULT_REWARD_RATE)
+}
+
+/// Reject new campaigns and donations while the platform is paused
+fn require_not_paused(env: &Env) {
+    if env.storage().instance().get(&DataKey::Paused).unwrap_or(false) {
+        panic!("Contract is paused");
+    }
+}
+
+/// Require auth from the stored platform admin
+fn require_admin(env: &Env) -> Address {
+    let admin: Address = env.storage().instance()
+        .get(&DataKey::Admin)
+        .expect("Admin not set");
+    admin.require_auth();
+    admin
+}
+
+/// Assets a campaign accepts: its own list, or the native asset if configured
+fn accepted_assets(env: &Env, campaign_id: u64) -> Vec<Address> {
+    if let Some(assets) = env.storage().instance().get(&DataKey::CampaignAssets(campaign_id)) {
+        return assets;
+    }
+    let mut assets = Vec::new(env);
+    if let Some(native) = env.storage().instance().get::<_, Address>(&DataKey::NativeAsset) {
+        assets.push_back(native);
+    }
+    assets
+}
+
+/// Assets a campaign holds funds in, even if no longer accepted
+fn held_assets(env: &Env, campaign_id: u64) -> Vec<Address> {
+    env.storage().instance()
+        .get(&CustodyKey::HeldAssets(campaign_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add to a campaign's raised total of an asset, remembering the asset for payouts
+fn credit_raised(env: &Env, campaign_id: u64, asset: &Address, amount: i128) {
+    let raised_key = DataKey::Raised(campaign_id, asset.clone());
+    let raised: i128 = env.storage().instance().get(&raised_key).unwrap_or(0);
+    env.storage().instance().set(
+        &raised_key,
+        &raised.checked_add(amount).expect("Donation overflow"),
+    );
+
+    let mut assets = held_assets(env, campaign_id);
+    if !assets.contains(asset) {
+        assets.push_back(asset.clone());
+        env.storage().instance().set(&CustodyKey::HeldAssets(campaign_id), &assets);
+    }
+}
+
+/// Value an asset amount in the oracle's base unit, rejecting stale prices
+fn quote_value(env: &Env, asset: &Address, amount: i128) -> i128 {
+    let config: OracleConfig = env.storage().instance()
+        .get(&DataKey::PriceOracle)
+        .expect("Price oracle not configured");
+
+    let oracle = PriceOracleClient::new(env, &config.oracle);
+    let price = oracle.lastprice(&Asset::Stellar(asset.clone()))
+        .expect("Price unavailable");
+
+    if price.price <= 0 {
+        panic!("Invalid oracle price");
+    }
+
+    if env.ledger().timestamp() > price.timestamp.saturating_add(config.max_age) {
+        panic!("Oracle price is stale");
+    }
+
+    let scale = 10i128.checked_pow(oracle.decimals()).expect("Oracle decimals overflow");
+    amount
+        .checked_mul(price.price)
+        .expect("Valuation overflow")
+        / scale
+}
+
+/// Raised funds of an asset not yet paid out
+fn available_funds(env: &Env, campaign_id: u64, asset: &Address) -> i128 {
+    let raised: i128 = env.storage().instance()
+        .get(&DataKey::Raised(campaign_id, asset.clone()))
+        .unwrap_or(0);
+    let withdrawn: i128 = env.storage().instance()
+        .get(&DataKey::Withdrawn(campaign_id, asset.clone()))
+        .unwrap_or(0);
+    raised - withdrawn
+}
+
+/// Suspend a campaign, freezing any payout stream at the current time
+fn suspend_campaign(env: &Env, campaign: &mut Campaign) {
+    let stream: Option<PayoutStream> = env.storage().instance()
+        .get(&DataKey::PayoutStream(campaign.id));
+    if let Some(mut stream) = stream.filter(|stream| stream.started_at.is_some()) {
+        // Freeze what the team is owed before refunds shrink the raised totals
+        for asset in held_assets(env, campaign.id).iter() {
+            let streamed = streamed_amount(env, campaign.id, &stream, &asset);
+            env.storage().instance().set(&DataKey::Streamed(campaign.id, asset), &streamed);
+        }
+        stream.stopped_at = Some(env.ledger().timestamp());
+        env.storage().instance().set(&DataKey::PayoutStream(campaign.id), &stream);
+    }
+
+    campaign.status = CampaignStatus::Suspended;
+    env.storage().instance().set(&DataKey::Campaign(campaign.id), &*campaign);
+    update_creator_stats(env, &campaign.creator, |stats| stats.campaigns_suspended += 1);
+
+    env.events().publish(
+        (symbol_short!("CAMPAIGN"), symbol_short!("suspended")),
+        campaign.id,
+    );
+}
+
+/// Deactivate a subscription so no further payments are pulled
+fn end_subscription(env: &Env, subscription: &mut Subscription) {
+    subscription.active = false;
+    env.storage().instance().set(&DataKey::Subscription(subscription.id), &*subscription);
+    env.storage().instance().extend_ttl(100_000, 100_000);
+
+    env.events().publish(
+        (symbol_short!("SUBSCR"), symbol_short!("ended")),
+        (subscription.id, subscription.campaign_id),
+    );
+}
+
+/// Load a dispute that is open for arbitration
+fn load_open_dispute(env: &Env, campaign_id: u64) -> Dispute {
+    let dispute: Dispute = env.storage().instance()
+        .get(&DataKey::Dispute(campaign_id))
+        .expect("Dispute not found");
+
+    if dispute.status != DisputeStatus::Open {
+        panic!("Dispute is not open");
+    }
+    dispute
+}
+
+/// Settle a dispute in favour of donors: suspend the campaign so they can claim refunds
+fn refund_dispute(env: &Env, dispute: &mut Dispute) {
+    dispute.status = DisputeStatus::Refunded;
+    env.storage().instance().set(&DataKey::Dispute(dispute.campaign_id), &*dispute);
+
+    let mut campaign = load_campaign(env, dispute.campaign_id);
+    suspend_campaign(env, &mut campaign);
+
+    let bond: Option<CampaignBond> = env.storage().instance()
+        .get(&DataKey::CampaignBond(dispute.campaign_id));
+    if bond.is_some_and(|bond| bond.status == BondStatus::Held) {
+        slash_bond(env, dispute.campaign_id);
+    }
+}
+
+/// Forfeit a held bond so donors can claim it pro-rata
+/// Bonds of campaigns without donations go to the platform treasury as fees
+fn slash_bond(env: &Env, campaign_id: u64) {
+    let key = DataKey::CampaignBond(campaign_id);
+    let mut bond: CampaignBond = env.storage().instance().get(&key).expect("No bond posted");
+    if bond.status != BondStatus::Held {
+        panic!("Bond already settled");
+    }
+
+    bond.status = BondStatus::Slashed;
+    env.storage().instance().set(&key, &bond);
+
+    let donations: Vec<Donation> = env.storage().instance()
+        .get(&DataKey::Donations(campaign_id))
+        .unwrap_or(Vec::new(env));
+    if donations.is_empty() {
+        let fees_key = DataKey::AccruedFees(bond.asset.clone());
+        let accrued: i128 = env.storage().instance().get(&fees_key).unwrap_or(0);
+        env.storage().instance().set(&fees_key, &(accrued + bond.amount));
+    }
+
+    env.events().publish(
+        (symbol_short!("BOND"), symbol_short!("slashed")),
+        (campaign_id, bond.amount),
+    );
+}
+
+/// Whether a payer acknowledged the current verification status, if the donation value needs it
+fn has_acknowledged(env: &Env, campaign: &Campaign, payer: &Address, value: i128) -> bool {
+    let ack_threshold: i128 = env.storage().instance()
+        .get(&DataKey::AckThreshold(campaign.id))
+        .unwrap_or(0);
+    if ack_threshold == 0 || value < ack_threshold {
+        return true;
+    }
+
+    let acknowledged: Option<Verification> = env.storage().instance()
+        .get(&DataKey::VerificationAck(campaign.id, payer.clone()));
+    acknowledged == Some(campaign_verification(env, campaign))
+}
+
+/// Allowlist an address for a private campaign in persistent storage
+fn allowlist(env: &Env, campaign_id: u64, donor: Address) {
+    let key = AllowlistKey::Allowlisted(campaign_id, donor);
+    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().extend_ttl(&key, 100_000, 100_000);
+}
+
+/// Whether an address may donate: any address for public campaigns, allowlisted ones for private
+fn may_donate(env: &Env, campaign_id: u64, address: &Address) -> bool {
+    let private: bool = env.storage().instance()
+        .get(&AllowlistKey::PrivateCampaign(campaign_id))
+        .unwrap_or(false);
+    if !private {
+        return true;
+    }
+
+    let key = AllowlistKey::Allowlisted(campaign_id, address.clone());
+    let allowed = env.storage().persistent().get(&key).unwrap_or(false);
+    if allowed {
+        env.storage().persistent().extend_ttl(&key, 100_000, 100_000);
+    }
+    allowed
+}
+
+/// Load a campaign after checking the caller is its creator
+fn load_creator_campaign(env: &Env, campaign_id: u64, caller: &Address) -> Campaign {
+    caller.require_auth();
+
+    let campaign = load_campaign(env, campaign_id);
+    if campaign.creator != *caller {
+        panic!("Only creator can manage allowlist");
+    }
+    campaign
+}
+
+/// Whether a subject holds a live attestation
+fn is_attested(env: &Env, subject: AttestationSubject) -> bool {
+    let attestation: Option<Attestation> = env.storage().instance()
+        .get(&DataKey::Attestation(subject));
+    attestation.is_some_and(|attestation| is_live(env, &attestation))
+}
+
+/// Whether an attestation is unrevoked, unexpired and from a current verifier
+fn is_live(env: &Env, attestation: &Attestation) -> bool {
+    !attestation.revoked
+        && env.ledger().timestamp() < attestation.expires_at
+        && CrowdfundingContract::get_verifiers(env.clone()).contains(&attestation.verifier)
+}
+
+/// Verification status of a campaign and its creator
+fn campaign_verification(env: &Env, campaign: &Campaign) -> Verification {
+    Verification {
+        creator_kyc: is_attested(env, AttestationSubject::Creator(campaign.creator.clone())),
+        registered_charity: is_attested(env, AttestationSubject::Charity(campaign.id)),
+    }
+}
+
+/// Load a campaign's payout stream or panic
+fn load_stream(env: &Env, campaign_id: u64) -> PayoutStream {
+    env.storage().instance()
+        .get(&DataKey::PayoutStream(campaign_id))
+        .expect("Campaign does not stream payouts")
+}
+
+/// Seconds of a payout stream elapsed so far, capped at its duration
+fn stream_elapsed(env: &Env, stream: &PayoutStream) -> u64 {
+    match stream.started_at {
+        Some(start) => stream.stopped_at
+            .unwrap_or(env.ledger().timestamp())
+            .saturating_sub(start)
+            .min(stream.duration),
+        None => 0,
+    }
+}
+
+/// Amount of an asset streamed to the team so far, including claimed funds
+fn streamed_amount(env: &Env, campaign_id: u64, stream: &PayoutStream, asset: &Address) -> i128 {
+    if stream.stopped_at.is_some() {
+        return env.storage().instance()
+            .get(&DataKey::Streamed(campaign_id, asset.clone()))
+            .unwrap_or(0);
+    }
+
+    let raised: i128 = env.storage().instance()
+        .get(&DataKey::Raised(campaign_id, asset.clone()))
+        .unwrap_or(0);
+    raised * stream_elapsed(env, stream) as i128 / stream.duration as i128
+}
+
+/// Portion of `amount` not yet streamed to the team; all of it without a stream
+fn unstreamed_share(env: &Env, campaign_id: u64, amount: i128) -> i128 {
+    let stream: Option<PayoutStream> = env.storage().instance()
+        .get(&DataKey::PayoutStream(campaign_id));
+    match stream {
+        Some(stream) => {
+            let remaining = stream.duration - stream_elapsed(env, &stream);
+            amount * remaining as i128 / stream.duration as i128
+        }
+        None => amount,
+    }
+}
+
+/// Release all of a funded campaign's available funds to its team
+fn withdraw_available(env: &Env, campaign: &Campaign) {
+    if campaign.status != CampaignStatus::Funded {
+        panic!("Campaign is not funded");
+    }
+
+    for asset in held_assets(env, campaign.id).iter() {
+        let available = available_funds(env, campaign.id, &asset);
+        if available <= 0 {
+            continue;
+        }
+
+        let withdrawn_key = DataKey::Withdrawn(campaign.id, asset.clone());
+        let withdrawn: i128 = env.storage().instance().get(&withdrawn_key).unwrap_or(0);
+        env.storage().instance().set(&withdrawn_key, &(withdrawn + available));
+
+        release_funds(env, campaign, &asset, available);
+    }
+}
+
+/// Load a campaign's signer set and require `signer` to be in it
+fn require_withdrawal_signer(env: &Env, campaign_id: u64, signer: &Address) -> WithdrawalSigners {
+    let config: WithdrawalSigners = env.storage().instance()
+        .get(&DataKey::WithdrawalSigners(campaign_id))
+        .expect("Campaign has no withdrawal signers");
+
+    if !config.signers.contains(signer) {
+        panic!("Not a withdrawal signer");
+    }
+    config
+}
+
+/// Load a withdrawal proposal that is neither executed nor expired
+fn load_pending_withdrawal(env: &Env, proposal_id: u64) -> WithdrawalProposal {
+    let proposal: WithdrawalProposal = env.storage().instance()
+        .get(&DataKey::WithdrawalProposal(proposal_id))
+        .expect("Withdrawal proposal not found");
+
+    if proposal.executed {
+        panic!("Withdrawal already executed");
+    }
+
+    if env.ledger().timestamp() > proposal.expires_at {
+        panic!("Withdrawal proposal expired");
+    }
+    proposal
+}
+
+/// Pay funds out to a campaign's team, deducting and accruing the platform fee
+/// Refunds must never go through this path
+fn release_funds(env: &Env, campaign: &Campaign, asset: &Address, amount: i128) -> i128 {
+    let campaign_id = campaign.id;
+
+    let dispute: Option<Dispute> = env.storage().instance().get(&DataKey::Dispute(campaign_id));
+    if dispute.is_some_and(|dispute| dispute.status == DisputeStatus::Open) {
+        panic!("Campaign funds are frozen by a dispute");
+    }
+
+    let config: Option<FeeConfig> = env.storage().instance().get(&DataKey::FeeConfig);
+    let fee = match config {
+        Some(config) => amount
+            .checked_mul(config.fee_bps as i128)
+            .expect("Fee overflow")
+            / 10_000,
+        None => 0,
+    };
+
+    if fee > 0 {
+        let fees_key = DataKey::AccruedFees(asset.clone());
+        let accrued: i128 = env.storage().instance().get(&fees_key).unwrap_or(0);
+        env.storage().instance().set(&fees_key, &(accrued + fee));
+
+        env.events().publish(
+            (symbol_short!("FEE"), symbol_short!("charged")),
+            (campaign_id, asset.clone(), fee),
+        );
+    }
+
+    // Split the net payout by share; the last member receives any rounding remainder
+    let net = amount - fee;
+    let team = campaign_team(env, campaign);
+    let token_client = token::Client::new(env, asset);
+    let mut paid = 0i128;
+    for (index, member) in team.iter().enumerate() {
+        let share = if index as u32 == team.len() - 1 {
+            net - paid
+        } else {
+            net * member.share_bps as i128 / 10_000
+        };
+        paid += share;
+
+        if share > 0 {
+            token_client.transfer(&env.current_contract_address(), &member.member, &share);
+        }
+
+        env.events().publish(
+            (symbol_short!("FUNDS"), symbol_short!("released")),
+            (campaign_id, member.member, asset.clone(), share),
+        );
+    }
+
+    net
+}
+
+/// Load a matching pool or panic
+fn load_pool(env: &Env, pool_id: u64) -> MatchingPool {
+    env.storage().instance()
+        .get(&DataKey::MatchingPool(pool_id))
+        .expect("Matching pool not found")
+}
+
+/// Store a pool's eligible campaigns and keep the per-campaign index in sync
+fn set_pool_campaigns(env: &Env, pool_id: u64, campaign_ids: Vec<u64>) {
+    let mut pool = load_pool(env, pool_id);
+
+    for campaign_id in pool.campaign_ids.iter() {
+        let mut pools: Vec<u64> = env.storage().instance()
+            .get(&DataKey::CampaignPools(campaign_id))
+            .unwrap_or(Vec::new(env));
+        if let Some(index) = pools.first_index_of(pool_id) {
+            pools.remove(index);
+        }
+        env.storage().instance().set(&DataKey::CampaignPools(campaign_id), &pools);
+    }
+
+    let mut unique_ids = Vec::new(env);
+    for campaign_id in campaign_ids.iter() {
+        load_campaign(env, campaign_id);
+        if unique_ids.contains(campaign_id) {
+            continue;
+        }
+        unique_ids.push_back(campaign_id);
+
+        let mut pools: Vec<u64> = env.storage().instance()
+            .get(&DataKey::CampaignPools(campaign_id))
+            .unwrap_or(Vec::new(env));
+        pools.push_back(pool_id);
+        env.storage().instance().set(&DataKey::CampaignPools(campaign_id), &pools);
+    }
+
+    pool.campaign_ids = unique_ids;
+    env.storage().instance().set(&DataKey::MatchingPool(pool_id), &pool);
+}
+
+/// Match a donation from every eligible pool, never exceeding the campaign target
+/// Returns the matched value credited to the campaign and the amount taken from each pool
+fn apply_matching(
+    env: &Env,
+    campaign_id: u64,
+    donor: &Address,
+    asset: &Address,
+    amount: i128,
+    value: i128,
+    mut remaining: i128,
+) -> (i128, Map<u64, i128>) {
+    let pool_ids: Vec<u64> = env.storage().instance()
+        .get(&DataKey::CampaignPools(campaign_id))
+        .unwrap_or(Vec::new(env));
+
+    let mut matches = Map::new(env);
+    let mut total_matched = 0i128;
+    for pool_id in pool_ids.iter() {
+        if remaining <= 0 {
+            break;
+        }
+
+        let mut pool = load_pool(env, pool_id);
+        if pool.asset != *asset || env.ledger().timestamp() > pool.expires_at {
+            continue;
+        }
+
+        let mut match_amount = amount
+            .checked_mul(pool.ratio_bps as i128)
+            .expect("Match overflow")
+            / 10_000;
+        match_amount = match_amount.min(pool.cap - pool.matched);
+        if match_amount <= 0 {
+            continue;
+        }
+
+        // Matches are valued at the same rate as the donation itself
+        let mut match_value = match_amount * value / amount;
+        if match_value > remaining {
+            match_amount = match_amount * remaining / match_value;
+            match_value = remaining;
+        }
+        if match_amount <= 0 || match_value <= 0 {
+            continue;
+        }
+
+        pool.matched += match_amount;
+        env.storage().instance().set(&DataKey::MatchingPool(pool_id), &pool);
+        credit_raised(env, campaign_id, asset, match_amount);
+
+        remaining -= match_value;
+        total_matched += match_value;
+        matches.set(pool_id, match_amount);
+
+        env.events().publish(
+            (symbol_short!("MATCH"), symbol_short!("applied")),
+            (pool_id, campaign_id, donor.clone(), match_amount, match_value),
+        );
+    }
+
+    (total_matched, matches)
+}
+
+/// Move part of a sponsor match back out of a campaign
+/// Funds return to the pool, or straight to the sponsor once the pool was reclaimed
+fn return_match(env: &Env, campaign_id: u64, asset: &Address, pool_id: u64, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+
+    let raised_key = DataKey::Raised(campaign_id, asset.clone());
+    let raised: i128 = env.storage().instance().get(&raised_key).unwrap_or(0);
+    env.storage().instance().set(&raised_key, &(raised - amount));
+
+    let mut pool = load_pool(env, pool_id);
+    pool.matched -= amount;
+    env.storage().instance().set(&DataKey::MatchingPool(pool_id), &pool);
+
+    if pool.reclaimed {
+        token::Client::new(env, asset)
+            .transfer(&env.current_contract_address(), &pool.sponsor, &amount);
+    }
+
+    env.events().publish(
+        (symbol_short!("MATCH"), symbol_short!("returned")),
+        (pool_id, campaign_id, amount),
+    );
+}
+
+/// Load a quadratic funding round or panic
+fn load_round(env: &Env, round_id: u64) -> QfRound {
+    env.storage().instance()
+        .get(&DataKey::QfRound(round_id))
+        .expect("Round not found")
+}
+
+/// Add an eligible campaign to a round and index the round by campaign
+fn add_round_campaign(env: &Env, round_id: u64, campaign_id: u64) {
+    load_campaign(env, campaign_id);
+
+    let mut round = load_round(env, round_id);
+    if round.campaign_ids.contains(campaign_id) {
+        return;
+    }
+    round.campaign_ids.push_back(campaign_id);
+    env.storage().instance().set(&DataKey::QfRound(round_id), &round);
+
+    let mut rounds: Vec<u64> = env.storage().instance()
+        .get(&DataKey::CampaignRounds(campaign_id))
+        .unwrap_or(Vec::new(env));
+    rounds.push_back(round_id);
+    env.storage().instance().set(&DataKey::CampaignRounds(campaign_id), &rounds);
+}
+
+/// Accumulate a donation into every active round the campaign belongs to
+fn record_round_contribution(env: &Env, campaign_id: u64, donor: &Address, value: i128) {
+    let rounds: Vec<u64> = env.storage().instance()
+        .get(&DataKey::CampaignRounds(campaign_id))
+        .unwrap_or(Vec::new(env));
+
+    let now = env.ledger().timestamp();
+    for round_id in rounds.iter() {
+        let round = load_round(env, round_id);
+        if now < round.start_time || now > round.end_time {
+            continue;
+        }
+
+        let key = DataKey::RoundContribution(round_id, campaign_id, donor.clone());
+        let contribution: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        if contribution == 0 {
+            let mut donors: Vec<Address> = env.storage().instance()
+                .get(&DataKey::RoundDonors(round_id, campaign_id))
+                .unwrap_or(Vec::new(env));
+            donors.push_back(donor.clone());
+            env.storage().instance().set(&DataKey::RoundDonors(round_id, campaign_id), &donors);
+        }
+        env.storage().instance().set(&key, &(contribution + value));
+    }
+}
+
+/// Remove retracted value from every active round the campaign belongs to
+fn reduce_round_contribution(env: &Env, campaign_id: u64, donor: &Address, value: i128) {
+    let rounds: Vec<u64> = env.storage().instance()
+        .get(&DataKey::CampaignRounds(campaign_id))
+        .unwrap_or(Vec::new(env));
+
+    let now = env.ledger().timestamp();
+    for round_id in rounds.iter() {
+        let round = load_round(env, round_id);
+        if now < round.start_time || now > round.end_time {
+            continue;
+        }
+
+        let key = DataKey::RoundContribution(round_id, campaign_id, donor.clone());
+        let contribution: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(contribution - value).max(0));
+    }
+}
+
+/// Integer square root (floor) using Newton's method
+fn isqrt(n: i128) -> i128 {
+    if n <= 0 {
+        return 0;
+    }
+    let mut x = n;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// Default SST minted per unit donated (10 SST per 1 XLM equivalent)
+const DEFAULT_REWARD_RATE: i128 = 10;
+
+/// Window in seconds after a donation during which it can be retracted
+const RETRACT_GRACE_PERIOD: u64 = 86_400;
+
+/// Maximum platform fee in basis points (10%)
+const MAX_FEE_BPS: u32 = 1_000;
+
+/// Seconds a withdrawal proposal stays open for approval (7 days)
+const WITHDRAWAL_PROPOSAL_TTL: u64 = 604_800;
+
+/// Default share of contributions, in basis points, needed to open a dispute (10%)
+const DEFAULT_DISPUTE_THRESHOLD_BPS: u32 = 1_000;
+
+/// Seconds arbiters have to resolve an open dispute (14 days)
+const DISPUTE_TIMEOUT: u64 = 1_209_600;
+
+/// Maximum length in bytes of a donation message
+const MAX_MESSAGE_LEN: u32 = 140;
+
+/// Load a donation of a funded campaign that selected a perk
+fn load_perk_donation(env: &Env, campaign: &Campaign, donation_index: u32) -> Donation {
+    if campaign.status != CampaignStatus::Funded {
+        panic!("Campaign is not funded");
+    }
+
+    let donations: Vec<Donation> = env.storage().instance()
+        .get(&DataKey::Donations(campaign.id))
+        .unwrap_or(Vec::new(env));
+    let donation = donations.get(donation_index).expect("Donation not found");
+
+    if donation.tier.is_none() {
+        panic!("Donation has no perk");
+    }
+    donation
+}
+
+/// Move a perk between fulfilment states and update the creator's counters
+fn set_fulfilment(
+    env: &Env,
+    campaign: &Campaign,
+    donation_index: u32,
+    from: FulfilmentStatus,
+    to: FulfilmentStatus,
+) {
+    env.storage().instance().set(&DataKey::Fulfilment(campaign.id, donation_index), &to);
+
+    update_creator_stats(env, &campaign.creator, |stats| {
+        if let Some(counter) = fulfilment_counter(stats, from) {
+            *counter -= 1;
+        }
+        if let Some(counter) = fulfilment_counter(stats, to) {
+            *counter += 1;
+        }
+    });
+    env.storage().instance().extend_ttl(100_000, 100_000);
+
+    env.events().publish(
+        (symbol_short!("PERK"), symbol_short!("status")),
+        (campaign.id, donation_index, to),
+    );
+}
+
+/// Team of a campaign, defaulting to the creator with the full share
+fn campaign_team(env: &Env, campaign: &Campaign) -> Vec<TeamMember> {
+    env.storage().instance()
+        .get(&DataKey::CampaignTeam(campaign.id))
+        .unwrap_or_else(|| {
+            Vec::from_array(env, [TeamMember { member: campaign.creator.clone(), share_bps: 10_000 }])
+        })
+}
+
+/// Whether an address is the creator or a co-creator of a campaign
+fn is_team_member(env: &Env, campaign: &Campaign, address: &Address) -> bool {
+    campaign_team(env, campaign).iter().any(|member| member.member == *address)
+}
+
+/// Apply a change to a creator's track record
+fn update_creator_stats(env: &Env, creator: &Address, update: impl FnOnce(&mut CreatorStats)) {
+    let key = DataKey::CreatorStats(creator.clone());
+    let mut stats: CreatorStats = env.storage().instance().get(&key).unwrap_or_default();
+    update(&mut stats);
+    env.storage().instance().set(&key, &stats);
+}
+
+/// Mark an open campaign past its deadline as expired
+fn expire_campaign(env: &Env, campaign: &mut Campaign) {
+    campaign.status = CampaignStatus::Expired;
+    env.storage().instance().set(&DataKey::Campaign(campaign.id), &*campaign);
+    update_creator_stats(env, &campaign.creator, |stats| stats.campaigns_failed += 1);
+
+    env.events().publish(
+        (symbol_short!("CAMPAIGN"), symbol_short!("expired")),
+        campaign.id,
+    );
+}
+
+/// Creator counter tracking perks in `status`, if any
+fn fulfilment_counter(stats: &mut CreatorStats, status: FulfilmentStatus) -> Option<&mut u32> {
+    match status {
+        FulfilmentStatus::Pending => None,
+        FulfilmentStatus::Shipped => Some(&mut stats.perks_shipped),
+        FulfilmentStatus::Confirmed => Some(&mut stats.perks_confirmed),
+        FulfilmentStatus::Disputed => Some(&mut stats.perks_disputed),
+    }
+}
+
+/// Reserve one unit of a pledge tier for a donation worth `value`
+fn claim_tier(env: &Env, campaign_id: u64, tier_id: u32, value: i128) {
+    let mut tiers: Vec<RewardTier> = env.storage().instance()
+        .get(&DataKey::RewardTiers(campaign_id))
+        .unwrap_or(Vec::new(env));
+    let mut tier = tiers.get(tier_id).expect("Reward tier not found");
+
+    if value < tier.min_amount {
+        panic!("Donation below tier minimum");
+    }
+
+    if tier.quantity > 0 && tier.claimed >= tier.quantity {
+        panic!("Reward tier sold out");
+    }
+
+    tier.claimed += 1;
+    tiers.set(tier_id, tier);
+    env.storage().instance().set(&DataKey::RewardTiers(campaign_id), &tiers);
+}
+
+/// Return a pledge tier unit after its donation is retracted or refunded
+fn release_tier(env: &Env, campaign_id: u64, tier_id: u32) {
+    let mut tiers: Vec<RewardTier> = env.storage().instance()
+        .get(&DataKey::RewardTiers(campaign_id))
+        .unwrap_or(Vec::new(env));
+    if let Some(mut tier) = tiers.get(tier_id).filter(|tier| tier.claimed > 0) {
+        tier.claimed -= 1;
+        tiers.set(tier_id, tier);
+        env.storage().instance().set(&DataKey::RewardTiers(campaign_id), &tiers);
+    }
+}
+
+/// Optional details attached to a donation
+#[derive(Clone, Default)]
+struct DonationDetails {
+    message: Option<String>,
+    anonymous: bool,
+    tier: Option<u32>,
+    payer: Option<Address>, // Funds the donation when different from the donor
+}
+
+/// Shared donation path: validation, custody, accounting, events and SST reward
+/// With `from_allowance`, funds are pulled using the payer's token allowance
+fn record_donation(
+    env: &Env,
+    campaign_id: u64,
+    donor: &Address,
+    asset: Option<Address>,
+    amount: i128,
+    from_allowance: bool,
+    details: DonationDetails,
+) {
+    require_not_paused(env);
+
+    if amount <= 0 {
+        panic!("Donation amount must be positive");
+    }
+
+    if let Some(message) = &details.message {
+        if message.len() > MAX_MESSAGE_LEN {
+            panic!("Donation message too long");
+        }
+    }
+
+    let mut campaign = load_campaign(env, campaign_id);
+
+    // STRICT ROLE SEPARATION
+    let payer = details.payer.clone().unwrap_or(donor.clone());
+    if is_team_member(env, &campaign, donor) || is_team_member(env, &campaign, &payer) {
+        panic!("Creator cannot donate to their own campaign");
+    }
+
+    // Private campaigns accept donations from allowlisted addresses only
+    if !may_donate(env, campaign_id, donor) || !may_donate(env, campaign_id, &payer) {
+        panic!("Donor not allowlisted");
+    }
+
+    // Check campaign status
+    if campaign.status != CampaignStatus::Open {
+        panic!("Campaign is not accepting donations");
+    }
+
+    // Check end time — auto-expire if deadline passed
+    if env.ledger().timestamp() > campaign.end_time {
+        expire_campaign(env, &mut campaign);
+        panic!("Campaign has expired");
+    }
+
+    // Multi-asset campaigns value every contribution through the oracle
+    let multi_asset = env.storage().instance().has(&DataKey::CampaignAssets(campaign_id));
+    if let Some(asset) = &asset {
+        if !accepted_assets(env, campaign_id).contains(asset) {
+            panic!("Asset not accepted by campaign");
+        }
+    } else if multi_asset {
+        panic!("Asset not accepted by campaign");
+    }
+
+    let value = match &asset {
+        Some(asset) if multi_asset => quote_value(env, asset, amount),
+        _ => amount,
+    };
+
+    if value <= 0 {
+        panic!("Donation value too small");
+    }
+
+    // Prevent overfunding
+    let remaining = campaign.target_amount - campaign.total_donated;
+    if value > remaining {
+        panic!("Donation exceeds target. Overfunding not allowed");
+    }
+
+    if let Some(tier_id) = details.tier {
+        claim_tier(env, campaign_id, tier_id, value);
+    }
+
+    // Large donations need an acknowledgement of the status as it stands now
+    if !has_acknowledged(env, &campaign, &payer, value) {
+        panic!("Donor must acknowledge verification status");
+    }
+
+    // Take custody of the donated funds
+    if let Some(asset) = &asset {
+        let token_client = token::Client::new(env, asset);
+        let contract = env.current_contract_address();
+        if from_allowance {
+            token_client.transfer_from(&contract, &payer, &contract, &amount);
+        } else {
+            token_client.transfer(&payer, &contract, &amount);
+        }
+
+        credit_raised(env, campaign_id, asset, amount);
+    }
+
+    // Update campaign
+    campaign.total_donated = campaign.total_donated
+        .checked_add(value)
+        .expect("Donation overflow");
+
+    // Quadratic funding rounds track unique per-donor contributions
+    record_round_contribution(env, campaign_id, donor, value);
+
+    // Sponsor matching pools top up the donation
+    let mut matches = Map::new(env);
+    let mut match_value = 0i128;
+    if let Some(asset) = &asset {
+        let remaining = campaign.target_amount - campaign.total_donated;
+        (match_value, matches) = apply_matching(env, campaign_id, donor, asset, amount, value, remaining);
+        campaign.total_donated += match_value;
+    }
+
+    // Auto-mark as Funded if target reached
+    if campaign.total_donated >= campaign.target_amount {
+        campaign.status = CampaignStatus::Funded;
+
+        // Every tier pledge, including this one, becomes a perk the creator owes
+        let pledged: Vec<Donation> = env.storage().instance()
+            .get(&DataKey::Donations(campaign_id))
+            .unwrap_or(Vec::new(env));
+        let perks = pledged.iter().filter(|donation| donation.tier.is_some()).count() as u32
+            + details.tier.is_some() as u32;
+
+        update_creator_stats(env, &campaign.creator, |stats| {
+            stats.campaigns_funded += 1;
+            stats.total_raised += campaign.total_donated;
+            stats.perks_owed += perks;
+        });
+
+        let stream: Option<PayoutStream> = env.storage().instance()
+            .get(&DataKey::PayoutStream(campaign_id));
+        if let Some(mut stream) = stream {
+            stream.started_at = Some(env.ledger().timestamp());
+            env.storage().instance().set(&DataKey::PayoutStream(campaign_id), &stream);
+        }
+
+        // Emit CampaignFunded event
+        env.events().publish(
+            (symbol_short!("CAMPAIGN"), symbol_short!("funded")),
+            campaign_id,
+        );
+    }
+
+    env.storage().instance().set(&DataKey::Campaign(campaign_id), &campaign);
+
+    // Inter-contract call: Mint SST reward tokens to donor
+    // Reward ratio defaults to 1 XLM donated = 10 SST tokens.
+    // A mint rejected by the token (supply cap, rate limits) leaves the donation unrewarded
+    // Donations at or above the vesting threshold receive a vesting position instead
+    let reward_token_addr: Option<Address> = env.storage().instance()
+        .get(&DataKey::RewardTokenAddr);
+
+    let vesting: Option<VestingPolicy> = env.storage().instance()
+        .get::<_, VestingPolicy>(&DataKey::VestingPolicy)
+        .filter(|policy| value >= policy.threshold);
+
+    // Anonymous donors are hidden from every event linking them to the campaign
+    let public_donor = if details.anonymous {
+        env.current_contract_address()
+    } else {
+        donor.clone()
+    };
+
+    let mut reward_amount = 0i128;
+    if let Some(token_addr) = reward_token_addr {
+        let requested = value.checked_mul(reward_rate(env)).expect("Reward overflow");
+        if requested > 0 {
+            // Liquid rewards take whatever room is left under the SST cap
+            let token_client = RewardTokenClient::new(env, &token_addr);
+            let minter = env.current_contract_address();
+            let minted = match &vesting {
+                Some(policy) => token_client
+                    .try_mint_vested(&minter, donor, &requested, &policy.cliff_ledgers, &policy.duration_ledgers),
+                None => token_client.try_mint_up_to(&minter, donor, &requested),
+            };
+            // Only what the token credited is recorded: debt settlement and the cap can reduce it
+            if let Ok(Ok(credited)) = minted {
+                reward_amount = credited;
+
+                // Emit TokensMinted event
+                env.events().publish(
+                    (symbol_short!("TOKEN"), symbol_short!("minted")),
+                    (public_donor.clone(), reward_amount, campaign_id),
+                );
+            } else {
+                env.events().publish(
+                    (symbol_short!("TOKEN"), symbol_short!("mintfail")),
+                    (public_donor.clone(), requested, campaign_id),
+                );
+            }
+        }
+    }
+
+    // Record donation
+    let donation = Donation {
+        donor: donor.clone(),
+        payer: payer.clone(),
+        amount,
+        timestamp: env.ledger().timestamp(),
+        asset: asset.clone(),
+        value,
+        message: details.message.clone(),
+        anonymous: details.anonymous,
+        reward: reward_amount,
+        vested: vesting.is_some() && reward_amount > 0,
+        tier: details.tier,
+        matches,
+        match_value,
+    };
+
+    let mut donations: Vec<Donation> = env.storage().instance()
+        .get(&DataKey::Donations(campaign_id))
+        .unwrap_or(Vec::new(env));
+    donations.push_back(donation);
+    env.storage().instance().set(&DataKey::Donations(campaign_id), &donations);
+
+    // Track wallet donations; anonymous ones stay out of the public index
+    if !details.anonymous {
+        let mut wallet_donations: Vec<u64> = env.storage().instance()
+            .get(&DataKey::WalletDonations(donor.clone()))
+            .unwrap_or(Vec::new(env));
+        wallet_donations.push_back(campaign_id);
+        env.storage().instance().set(&DataKey::WalletDonations(donor.clone()), &wallet_donations);
+    }
+
+    env.storage().instance().extend_ttl(100_000, 100_000);
+
+    // Emit DonationMade event
+    env.events().publish(
+        (symbol_short!("DONATE"), symbol_short!("received")),
+        (
+            campaign_id,
+            public_donor.clone(),
+            value,
+            campaign.total_donated,
+            details.message,
+            details.anonymous,
+        ),
+    );
+
+    if multi_asset {
+        if let Some(asset) = asset {
+            env.events().publish(
+                (symbol_short!("DONATE"), symbol_short!("asset")),
+                (campaign_id, public_donor, asset, amount, value),
+            );
+        }
+    }
+
+    log!(env, "Donation {} to campaign {}", value, campaign_id);
+}
+
+/// Test suite
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Events, Ledger};
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+    use soroban_sdk::{vec, IntoVal};
+
+    #[test]
+    fn test_create_campaign() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let title = String::from_str(&env, "Test Campaign");
+        let target = 1_000_000_000i128;
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &target, &30);
+
+        assert_eq!(campaign_id, 1);
+        let campaign = client.get_campaign(&campaign_id).unwrap();
+        assert_eq!(campaign.status, CampaignStatus::Open);
+    }
+
+    #[test]
+    #[should_panic(expected = "Creator cannot donate to their own campaign")]
+    fn test_creator_cannot_donate() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let title = String::from_str(&env, "Test Campaign");
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &1_000_000_000i128, &30);
+        client.donate(&campaign_id, &creator, &100_000_000i128, &None, &false, &None);
+    }
+
+    #[test]
+    fn test_multi_wallet_donations() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let donor1 = Address::generate(&env);
+        let donor2 = Address::generate(&env);
+        let title = String::from_str(&env, "Test Campaign");
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &1_000_000_000i128, &30);
+        client.donate(&campaign_id, &donor1, &200_000_000i128, &None, &false, &None);
+        client.donate(&campaign_id, &donor2, &300_000_000i128, &None, &false, &None);
+
+        let campaign = client.get_campaign(&campaign_id).unwrap();
+        assert_eq!(campaign.total_donated, 500_000_000i128);
+    }
+
+    #[test]
+    #[should_panic(expected = "Donation exceeds target. Overfunding not allowed")]
+    fn test_overfunding_prevention() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Test Campaign");
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &1_000_000_000i128, &30);
+        client.donate(&campaign_id, &donor, &1_500_000_000i128, &None, &false, &None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Campaign is not accepting donations")]
+    fn test_donation_after_close() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Test Campaign");
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &1_000_000_000i128, &30);
+        client.close_campaign(&campaign_id, &creator);
+        client.donate(&campaign_id, &donor, &100_000_000i128, &None, &false, &None);
+    }
+
+    #[test]
+    fn test_auto_funded_status() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Test Campaign");
+        let target = 1_000_000_000i128;
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &target, &30);
+        client.donate(&campaign_id, &donor, &target, &None, &false, &None);
+
+        let campaign = client.get_campaign(&campaign_id).unwrap();
+        assert_eq!(campaign.status, CampaignStatus::Funded);
+    }
+
+    /// Minimal SEP-40 oracle for tests
+    #[contract]
+    pub struct MockOracle;
+
+    #[contractimpl]
+    impl MockOracle {
+        pub fn set_price(env: Env, asset: Address, price: i128, timestamp: u64) {
+            env.storage().instance().set(&asset, &PriceData { price, timestamp });
+        }
+
+        pub fn decimals(_env: Env) -> u32 {
+            7
+        }
+
+        pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+            match asset {
+                Asset::Stellar(addr) => env.storage().instance().get(&addr),
+                Asset::Other(_) => None,
+            }
+        }
+    }
+
+    /// Register a campaign accepting two oracle-priced assets
+    fn setup_multi_asset(env: &Env) -> (CrowdfundingContractClient<'_>, u64, Address, Address, MockOracleClient<'_>) {
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(env, &contract_id);
+        let oracle_id = env.register_contract(None, MockOracle);
+        let oracle = MockOracleClient::new(env, &oracle_id);
+
+        let admin = Address::generate(env);
+        let creator = Address::generate(env);
+        let asset_a = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let asset_b = env.register_stellar_asset_contract_v2(admin.clone()).address();
+
+        env.mock_all_auths();
+        client.set_admin(&admin);
+        client.set_price_oracle(&oracle_id, &300);
+
+        // 1 A = 2.0 USD, 1 B = 0.5 USD
+        oracle.set_price(&asset_a, &20_000_000i128, &0);
+        oracle.set_price(&asset_b, &5_000_000i128, &0);
+
+        let title = String::from_str(env, "Multi Asset");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.set_accepted_assets(&campaign_id, &creator, &vec![env, asset_a.clone(), asset_b.clone()]);
+
+        (client, campaign_id, asset_a, asset_b, oracle)
+    }
+
+    #[test]
+    fn test_multi_asset_donation_valued_by_oracle() {
+        let env = Env::default();
+        let (client, campaign_id, asset_a, asset_b, _) = setup_multi_asset(&env);
+
+        let donor = Address::generate(&env);
+        StellarAssetClient::new(&env, &asset_a).mint(&donor, &100);
+        StellarAssetClient::new(&env, &asset_b).mint(&donor, &400);
+
+        client.donate_asset(&campaign_id, &donor, &asset_a, &100i128, &None, &false, &None);
+        client.donate_asset(&campaign_id, &donor, &asset_b, &400i128, &None, &false, &None);
+
+        let campaign = client.get_campaign(&campaign_id).unwrap();
+        assert_eq!(campaign.total_donated, 400i128);
+        assert_eq!(client.get_raised_by_asset(&campaign_id, &asset_a), 100i128);
+        assert_eq!(client.get_raised_by_asset(&campaign_id, &asset_b), 400i128);
+        assert_eq!(TokenClient::new(&env, &asset_a).balance(&client.address), 100i128);
+
+        let donations = client.get_donations_by_campaign(&campaign_id);
+        assert_eq!(donations.get(1).unwrap().value, 200i128);
+    }
+
+    #[test]
+    #[should_panic(expected = "Oracle price is stale")]
+    fn test_stale_oracle_price() {
+        let env = Env::default();
+        let (client, campaign_id, asset_a, _, _) = setup_multi_asset(&env);
+
+        let donor = Address::generate(&env);
+        StellarAssetClient::new(&env, &asset_a).mint(&donor, &100);
+
+        env.ledger().set_timestamp(301);
+        client.donate_asset(&campaign_id, &donor, &asset_a, &100i128, &None, &false, &None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Asset not accepted by campaign")]
+    fn test_unaccepted_asset() {
+        let env = Env::default();
+        let (client, campaign_id, _, _, oracle) = setup_multi_asset(&env);
+
+        let admin = Address::generate(&env);
+        let other = env.register_stellar_asset_contract_v2(admin).address();
+        oracle.set_price(&other, &10_000_000i128, &0);
+
+        let donor = Address::generate(&env);
+        client.donate_asset(&campaign_id, &donor, &other, &100i128, &None, &false, &None);
+    }
+
+    /// Register a contract whose `donate` moves a Stellar asset
+    fn setup_native_asset(env: &Env) -> (CrowdfundingContractClient<'_>, Address) {
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(env, &contract_id);
+
+        let admin = Address::generate(env);
+        let asset = env.register_stellar_asset_contract_v2(admin.clone()).address();
+
+        env.mock_all_auths();
+        client.set_admin(&admin);
+        client.set_native_asset(&asset);
+
+        (client, asset)
+    }
+
+    #[test]
+    fn test_subscription_payments_and_skipped_periods() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Monthly Support");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &500);
+        TokenClient::new(&env, &asset).approve(&donor, &client.address, &500, &1_000);
+
+        let subscription_id = client.subscribe(&campaign_id, &donor, &asset, &100i128, &100);
+        assert!(client.process_subscription(&subscription_id));
+
+        // Two full periods pass unprocessed before the next pull
+        env.ledger().set_timestamp(350);
+        assert!(client.process_subscription(&subscription_id));
+
+        let subscription = client.get_subscription(&subscription_id).unwrap();
+        assert_eq!(subscription.payments_made, 2);
+        assert_eq!(subscription.skipped_periods, 2);
+        assert_eq!(subscription.next_payment, 400);
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().total_donated, 200i128);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&client.address), 200i128);
+
+        // Closing the campaign ends the subscription on the next trigger
+        client.close_campaign(&campaign_id, &creator);
+        env.ledger().set_timestamp(400);
+        assert!(!client.process_subscription(&subscription_id));
+        assert!(!client.get_subscription(&subscription_id).unwrap().active);
+    }
+
+    #[test]
+    fn test_subscription_stops_at_target() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Monthly Support");
+        let campaign_id = client.create_campaign(&creator, &title, &250i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &500);
+        token.approve(&donor, &client.address, &500, &1_000);
+
+        let subscription_id = client.subscribe(&campaign_id, &donor, &asset, &100i128, &100);
+        assert!(client.process_subscription(&subscription_id));
+        env.ledger().set_timestamp(100);
+        assert!(client.process_subscription(&subscription_id));
+
+        // Only the 50 still needed is charged, and the subscription ends with funding
+        env.ledger().set_timestamp(200);
+        assert!(client.process_subscription(&subscription_id));
+        assert_eq!(token.balance(&donor), 250i128);
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().status, CampaignStatus::Funded);
+        assert!(!client.get_subscription(&subscription_id).unwrap().active);
+    }
+
+    #[test]
+    fn test_subscription_ends_when_unpaid() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Monthly Support");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &150);
+        token.approve(&donor, &client.address, &500, &1_000);
+
+        let subscription_id = client.subscribe(&campaign_id, &donor, &asset, &100i128, &100);
+        assert!(client.process_subscription(&subscription_id));
+
+        // The balance no longer covers a payment, so the subscription ends
+        env.ledger().set_timestamp(100);
+        assert!(!client.process_subscription(&subscription_id));
+        assert!(!client.get_subscription(&subscription_id).unwrap().active);
+        assert_eq!(token.balance(&donor), 50i128);
+    }
+
+    #[test]
+    fn test_subscription_ends_when_donor_loses_access() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        let title = String::from_str(&env, "Private Support");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.set_campaign_private(&campaign_id, &creator, &true);
+        client.add_to_allowlist(&campaign_id, &creator, &vec![&env, donor.clone()]);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &500);
+        TokenClient::new(&env, &asset).approve(&donor, &client.address, &500, &1_000);
+
+        // Only allowlisted donors can subscribe to a private campaign
+        assert!(client.try_subscribe(&campaign_id, &outsider, &asset, &100i128, &100).is_err());
+        let subscription_id = client.subscribe(&campaign_id, &donor, &asset, &100i128, &100);
+        assert!(client.process_subscription(&subscription_id));
+
+        // Losing access ends the subscription instead of failing every trigger
+        client.remove_from_allowlist(&campaign_id, &creator, &vec![&env, donor.clone()]);
+        env.ledger().set_timestamp(100);
+        assert!(!client.process_subscription(&subscription_id));
+        assert!(!client.get_subscription(&subscription_id).unwrap().active);
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().total_donated, 100i128);
+    }
+
+    #[test]
+    #[should_panic(expected = "Subscription payment not due")]
+    fn test_subscription_not_due() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Monthly Support");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &500);
+        TokenClient::new(&env, &asset).approve(&donor, &client.address, &500, &1_000);
+
+        let subscription_id = client.subscribe(&campaign_id, &donor, &asset, &100i128, &100);
+        client.process_subscription(&subscription_id);
+
+        env.ledger().set_timestamp(50);
+        client.process_subscription(&subscription_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Subscription is not active")]
+    fn test_cancelled_subscription() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Monthly Support");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        let subscription_id = client.subscribe(&campaign_id, &donor, &asset, &100i128, &100);
+        client.cancel_subscription(&subscription_id, &donor);
+        client.process_subscription(&subscription_id);
+    }
+
+    #[test]
+    fn test_anonymous_donation_with_message() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Test Campaign");
+        let message = String::from_str(&env, "Good luck!");
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &1_000_000_000i128, &30);
+        client.donate(&campaign_id, &donor, &100_000_000i128, &Some(message.clone()), &true, &None);
+
+        let donation = client.get_donations_by_campaign(&campaign_id).get(0).unwrap();
+        assert_eq!(donation.donor, contract_id);
+        assert_eq!(donation.message, Some(message));
+        assert!(donation.anonymous);
+
+        // The public wallet index does not link the donor to the campaign
+        assert_eq!(client.get_donations_by_wallet(&donor).len(), 0);
+
+        // Neither do the retraction and refund events
+        client.retract_donation(&campaign_id, &donor, &40_000_000i128);
+        let events = env.events().all();
+        assert_eq!(
+            events.slice(events.len() - 1..),
+            vec![&env, (
+                contract_id.clone(),
+                (symbol_short!("DONATE"), symbol_short!("retracted")).into_val(&env),
+                (campaign_id, contract_id.clone(), 40_000_000i128, 60_000_000i128).into_val(&env),
+            )],
+        );
+
+        client.close_campaign(&campaign_id, &creator);
+        client.claim_refund(&campaign_id, &donor);
+        let events = env.events().all();
+        assert_eq!(
+            events.slice(events.len() - 1..),
+            vec![&env, (
+                contract_id.clone(),
+                (symbol_short!("DONATE"), symbol_short!("refunded")).into_val(&env),
+                (campaign_id, contract_id.clone(), 60_000_000i128).into_val(&env),
+            )],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Donation message too long")]
+    fn test_donation_message_too_long() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Test Campaign");
+        let message = String::from_bytes(&env, &[b'x'; 141]);
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &1_000_000_000i128, &30);
+        client.donate(&campaign_id, &donor, &100_000_000i128, &Some(message), &false, &None);
+    }
+
+    #[test]
+    fn test_donate_for_credits_beneficiary() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let payer = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let title = String::from_str(&env, "Gift Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&payer, &300);
+        client.donate_for(&campaign_id, &payer, &beneficiary, &300i128, &None);
+
+        let donation = client.get_donations_by_campaign(&campaign_id).get(0).unwrap();
+        assert_eq!(donation.donor, beneficiary);
+        assert_eq!(client.get_donations_by_wallet(&beneficiary), vec![&env, campaign_id]);
+        assert_eq!(client.get_donations_by_wallet(&payer).len(), 0);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&payer), 0i128);
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().total_donated, 300i128);
+        assert_eq!(donation.payer, payer);
+
+        // Retracting a gift returns the funds to the payer, not the beneficiary
+        client.retract_donation(&campaign_id, &beneficiary, &100i128);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&payer), 100i128);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&beneficiary), 0i128);
+    }
+
+    #[test]
+    #[should_panic(expected = "Creator cannot donate to their own campaign")]
+    fn test_creator_cannot_gift_own_campaign() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let title = String::from_str(&env, "Test Campaign");
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &1_000_000_000i128, &30);
+        client.donate_for(&campaign_id, &creator, &beneficiary, &100_000_000i128, &None);
+    }
+
+    #[test]
+    fn test_pledge_tiers() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let donor1 = Address::generate(&env);
+        let donor2 = Address::generate(&env);
+        let title = String::from_str(&env, "Tier Campaign");
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &10_000i128, &30);
+        let stickers = BytesN::from_array(&env, &[1; 32]);
+        let tier = client.add_reward_tier(&campaign_id, &creator, &50i128, &stickers, &1);
+
+        // Below the minimum is rejected
+        assert!(client.try_donate(&campaign_id, &donor1, &40i128, &None, &false, &Some(tier)).is_err());
+
+        client.donate(&campaign_id, &donor1, &60i128, &None, &false, &Some(tier));
+        assert_eq!(client.get_reward_tiers(&campaign_id).get(0).unwrap().claimed, 1);
+
+        // Limited to one selection
+        assert!(client.try_donate(&campaign_id, &donor2, &60i128, &None, &false, &Some(tier)).is_err());
+
+        let selections = client.get_tier_donations(&campaign_id, &tier);
+        assert_eq!(selections.len(), 1);
+        assert_eq!(selections.get(0).unwrap().donor, donor1);
+
+        // Retracting below the minimum frees the perk for another donor
+        client.retract_donation(&campaign_id, &donor1, &20i128);
+        assert_eq!(client.get_tier_donations(&campaign_id, &tier).len(), 0);
+        client.donate(&campaign_id, &donor2, &60i128, &None, &false, &Some(tier));
+        assert_eq!(client.get_tier_donations(&campaign_id, &tier).get(0).unwrap().donor, donor2);
+    }
+
+    #[test]
+    fn test_gifted_tier_released_on_refund() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let payer = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+        let title = String::from_str(&env, "Tier Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &10_000i128, &30);
+        let tier = client.add_reward_tier(&campaign_id, &creator, &50i128, &BytesN::from_array(&env, &[1; 32]), &1);
+
+        StellarAssetClient::new(&env, &asset).mint(&payer, &60);
+        client.donate_for(&campaign_id, &payer, &beneficiary, &60i128, &Some(tier));
+        assert_eq!(client.get_tier_donations(&campaign_id, &tier).get(0).unwrap().donor, beneficiary);
+        assert_eq!(client.get_reward_tiers(&campaign_id).get(0).unwrap().claimed, 1);
+
+        client.close_campaign(&campaign_id, &creator);
+        client.claim_refund(&campaign_id, &beneficiary);
+        assert_eq!(client.get_reward_tiers(&campaign_id).get(0).unwrap().claimed, 0);
+    }
+
+    #[test]
+    fn test_perk_fulfilment() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let donor1 = Address::generate(&env);
+        let donor2 = Address::generate(&env);
+        let title = String::from_str(&env, "Perk Campaign");
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &200i128, &30);
+        let tier = client.add_reward_tier(&campaign_id, &creator, &50i128, &BytesN::from_array(&env, &[1; 32]), &0);
+
+        client.donate(&campaign_id, &donor1, &100i128, &None, &false, &Some(tier));
+        // Perks can only be shipped once the campaign funds
+        assert!(client.try_mark_shipped(&campaign_id, &creator, &0).is_err());
+        client.donate(&campaign_id, &donor2, &100i128, &None, &false, &Some(tier));
+
+        client.mark_shipped(&campaign_id, &creator, &0);
+        client.confirm_fulfilment(&campaign_id, &donor1, &0);
+        assert_eq!(client.get_fulfilment(&campaign_id, &0), FulfilmentStatus::Confirmed);
+
+        client.mark_shipped(&campaign_id, &creator, &1);
+        assert!(client.try_confirm_fulfilment(&campaign_id, &donor1, &1).is_err());
+        client.dispute_fulfilment(&campaign_id, &donor2, &1);
+
+        let stats = client.get_creator_stats(&creator);
+        assert_eq!(stats.perks_owed, 2);
+        assert_eq!(stats.perks_shipped, 0);
+        assert_eq!(stats.perks_confirmed, 1);
+        assert_eq!(stats.perks_disputed, 1);
+
+        // Re-shipping after a dispute
+        client.mark_shipped(&campaign_id, &creator, &1);
+        assert_eq!(client.get_creator_stats(&creator).perks_shipped, 1);
+        assert_eq!(client.get_creator_stats(&creator).perks_disputed, 0);
+    }
+
+    #[test]
+    fn test_creator_stats() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Stats Campaign");
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &1_000);
+        let funded = client.create_campaign(&creator, &title, &300i128, &30);
+        client.donate(&funded, &donor, &300i128, &None, &false, &None);
+
+        let expired = client.create_campaign(&creator, &title, &1_000i128, &1);
+        client.donate(&expired, &donor, &100i128, &None, &false, &None);
+
+        let closed = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.close_campaign(&closed, &creator);
+
+        env.ledger().set_timestamp(86_401);
+        assert!(client.check_expired(&expired));
+        client.claim_refund(&expired, &donor);
+
+        let stats = client.get_creator_stats(&creator);
+        assert_eq!(stats.campaigns_launched, 3);
+        assert_eq!(stats.campaigns_funded, 1);
+        assert_eq!(stats.campaigns_failed, 2);
+        assert_eq!(stats.total_raised, 300i128);
+        assert_eq!(stats.refunds_issued, 1);
+
+        // Refunds from a funded campaign come off the raised total
+        let arbiter = Address::generate(&env);
+        client.add_arbiter(&arbiter);
+        client.raise_dispute(&funded, &donor);
+        client.resolve_dispute(&funded, &arbiter, &true);
+        client.claim_refund(&funded, &donor);
+
+        let stats = client.get_creator_stats(&creator);
+        assert_eq!(stats.total_raised, 0i128);
+        assert_eq!(stats.refunds_issued, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only creator can add reward tiers")]
+    fn test_add_reward_tier_not_creator() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let other = Address::generate(&env);
+        let title = String::from_str(&env, "Tier Campaign");
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &10_000i128, &30);
+        client.add_reward_tier(&campaign_id, &other, &50i128, &BytesN::from_array(&env, &[1; 32]), &0);
+    }
+
+    #[test]
+    fn test_matching_pool_matches_and_reclaims() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let sponsor = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Matched Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&sponsor, &150);
+        StellarAssetClient::new(&env, &asset).mint(&donor, &300);
+
+        // 1:1 match capped at 150
+        let pool_id = client.create_matching_pool(
+            &sponsor, &asset, &150i128, &10_000, &vec![&env, campaign_id], &1_000,
+        );
+
+        client.donate(&campaign_id, &donor, &100i128, &None, &false, &None);
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().total_donated, 200i128);
+
+        // Only 50 left in the pool
+        client.donate(&campaign_id, &donor, &100i128, &None, &false, &None);
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().total_donated, 350i128);
+        assert_eq!(client.get_matching_pool(&pool_id).unwrap().matched, 150i128);
+        assert_eq!(client.get_raised_by_asset(&campaign_id, &asset), 350i128);
+
+        // Exhausted pool no longer matches
+        client.donate(&campaign_id, &donor, &100i128, &None, &false, &None);
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().total_donated, 450i128);
+
+        env.ledger().set_timestamp(1_001);
+        assert_eq!(client.reclaim_pool(&pool_id, &sponsor), 0i128);
+        assert_eq!(token.balance(&client.address), 450i128);
+    }
+
+    #[test]
+    fn test_retraction_and_refund_return_matches() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let sponsor = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Matched Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&sponsor, &150);
+        StellarAssetClient::new(&env, &asset).mint(&donor, &100);
+
+        let pool_id = client.create_matching_pool(
+            &sponsor, &asset, &150i128, &10_000, &vec![&env, campaign_id], &3_000_000,
+        );
+        client.donate(&campaign_id, &donor, &100i128, &None, &false, &None);
+        assert_eq!(client.get_donations_by_campaign(&campaign_id).get(0).unwrap().match_value, 100i128);
+
+        // Retracting half the donation gives back half the match
+        client.retract_donation(&campaign_id, &donor, &50i128);
+        assert_eq!(client.get_matching_pool(&pool_id).unwrap().matched, 50i128);
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().total_donated, 100i128);
+        assert_eq!(client.get_raised_by_asset(&campaign_id, &asset), 100i128);
+
+        // The refund of the failed campaign releases the rest of the match
+        env.ledger().set_timestamp(31 * 86_400);
+        assert_eq!(client.claim_refund(&campaign_id, &donor), 50i128);
+        assert_eq!(client.get_matching_pool(&pool_id).unwrap().matched, 0i128);
+        assert_eq!(client.get_raised_by_asset(&campaign_id, &asset), 0i128);
+        assert_eq!(token.balance(&donor), 100i128);
+
+        env.ledger().set_timestamp(3_000_001);
+        assert_eq!(client.reclaim_pool(&pool_id, &sponsor), 150i128);
+        assert_eq!(token.balance(&client.address), 0i128);
+    }
+
+    #[test]
+    fn test_matching_never_overfunds() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let sponsor = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Matched Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &300i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&sponsor, &1_000);
+        StellarAssetClient::new(&env, &asset).mint(&donor, &200);
+
+        // 2:1 match would add 400 but only 100 remains after the donation
+        let pool_id = client.create_matching_pool(
+            &sponsor, &asset, &1_000i128, &20_000, &vec![&env, campaign_id], &1_000,
+        );
+        client.donate(&campaign_id, &donor, &200i128, &None, &false, &None);
+
+        let campaign = client.get_campaign(&campaign_id).unwrap();
+        assert_eq!(campaign.total_donated, 300i128);
+        assert_eq!(campaign.status, CampaignStatus::Funded);
+
+        env.ledger().set_timestamp(1_001);
+        assert_eq!(client.reclaim_pool(&pool_id, &sponsor), 900i128);
+        assert_eq!(token.balance(&sponsor), 900i128);
+    }
+
+    #[test]
+    #[should_panic(expected = "Matching pool has not expired")]
+    fn test_reclaim_pool_before_expiry() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let sponsor = Address::generate(&env);
+        let title = String::from_str(&env, "Matched Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&sponsor, &100);
+        let pool_id = client.create_matching_pool(
+            &sponsor, &asset, &100i128, &10_000, &vec![&env, campaign_id], &1_000,
+        );
+        client.reclaim_pool(&pool_id, &sponsor);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(24), 4);
+        assert_eq!(isqrt(25), 5);
+        assert_eq!(isqrt(1_000_000_000_000), 1_000_000);
+    }
+
+    #[test]
+    fn test_qf_round_distribution() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let sponsor = Address::generate(&env);
+        let creator_a = Address::generate(&env);
+        let creator_b = Address::generate(&env);
+        let asset = env.register_stellar_asset_contract_v2(admin).address();
+        let token = TokenClient::new(&env, &asset);
+
+        env.mock_all_auths();
+        StellarAssetClient::new(&env, &asset).mint(&sponsor, &200);
+
+        let title = String::from_str(&env, "QF Campaign");
+        let campaign_a = client.create_campaign(&creator_a, &title, &100i128, &30);
+        let campaign_b = client.create_campaign(&creator_b, &title, &10_000i128, &30);
+        let round_id = client.create_qf_round(
+            &sponsor, &asset, &200i128, &0, &1_000, &vec![&env, campaign_a, campaign_b],
+        );
+
+        // Campaign A: four donors of 25 -> (4 * 5)^2 - 100 = 300
+        for _ in 0..4 {
+            let donor = Address::generate(&env);
+            client.donate(&campaign_a, &donor, &25i128, &None, &false, &None);
+        }
+
+        // Campaign B: donors of 36 and 64 -> (6 + 8)^2 - 100 = 96
+        let donor = Address::generate(&env);
+        client.donate(&campaign_b, &donor, &36i128, &None, &false, &None);
+        let whale = Address::generate(&env);
+        client.donate(&campaign_b, &whale, &30i128, &None, &false, &None);
+        client.donate(&campaign_b, &whale, &34i128, &None, &false, &None);
+        assert_eq!(client.get_round_contribution(&round_id, &campaign_b, &whale), 64i128);
+
+        env.ledger().set_timestamp(1_001);
+
+        // Donations after the window do not count
+        let late = Address::generate(&env);
+        client.donate(&campaign_b, &late, &900i128, &None, &false, &None);
+
+        // Subsidies of 396 exceed the pool of 200 and are scaled down
+        client.finalize_qf_round(&round_id);
+        assert_eq!(client.get_round_match(&round_id, &campaign_a), 151i128);
+        assert_eq!(client.get_round_match(&round_id, &campaign_b), 48i128);
+        assert_eq!(token.balance(&sponsor), 1i128);
+
+        // The match joins the campaign's funds instead of going straight to the team
+        assert_eq!(client.claim_qf_match(&round_id, &campaign_a, &creator_a), 151i128);
+        assert_eq!(client.get_available_funds(&campaign_a, &asset), 151i128);
+        assert_eq!(token.balance(&creator_a), 0i128);
+        assert_eq!(client.get_round_match(&round_id, &campaign_a), 0i128);
+
+        // Unfunded campaigns cannot claim; the sponsor recovers the match once they fail
+        assert!(client.try_claim_qf_match(&round_id, &campaign_b, &creator_b).is_err());
+        assert!(client.try_reclaim_qf_match(&round_id, &campaign_b, &sponsor).is_err());
+        env.ledger().set_timestamp(31 * 86_400);
+        assert_eq!(client.reclaim_qf_match(&round_id, &campaign_b, &sponsor), 48i128);
+        assert_eq!(token.balance(&sponsor), 49i128);
+    }
+
+    #[test]
+    #[should_panic(expected = "Round not finalized")]
+    fn test_qf_claim_before_finalize() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let sponsor = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let asset = env.register_stellar_asset_contract_v2(admin).address();
+
+        env.mock_all_auths();
+        StellarAssetClient::new(&env, &asset).mint(&sponsor, &1_000);
+
+        let title = String::from_str(&env, "QF Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &10_000i128, &30);
+        let round_id = client.create_qf_round(
+            &sponsor, &asset, &1_000i128, &0, &1_000, &vec![&env, campaign_id],
+        );
+        client.claim_qf_match(&round_id, &campaign_id, &creator);
+    }
+
+    #[test]
+    fn test_withdraw_charges_platform_fee() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let title = String::from_str(&env, "Fee Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        // 2.5% platform fee
+        client.set_platform_fee(&250, &treasury);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &1_000);
+        client.donate(&campaign_id, &donor, &1_000i128, &None, &false, &None);
+        client.withdraw(&campaign_id, &creator);
+
+        assert_eq!(token.balance(&creator), 975i128);
+        assert_eq!(client.get_accrued_fees(&asset), 25i128);
+        assert_eq!(client.get_available_funds(&campaign_id, &asset), 0i128);
+
+        assert_eq!(client.withdraw_fees(&asset), 25i128);
+        assert_eq!(token.balance(&treasury), 25i128);
+        assert_eq!(client.get_accrued_fees(&asset), 0i128);
+    }
+
+    #[test]
+    fn test_withdraw_after_native_asset_change() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Migrating Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &1_000);
+        client.donate(&campaign_id, &donor, &1_000i128, &None, &false, &None);
+
+        // Funds already held in the old native asset are still paid out
+        let new_asset = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        client.set_native_asset(&new_asset);
+        client.withdraw(&campaign_id, &creator);
+
+        assert_eq!(TokenClient::new(&env, &asset).balance(&creator), 1_000i128);
+        assert_eq!(client.get_available_funds(&campaign_id, &asset), 0i128);
+    }
+
+    #[test]
+    #[should_panic(expected = "Campaign is not open")]
+    fn test_close_funded_campaign() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Funded Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &1_000);
+        client.donate(&campaign_id, &donor, &1_000i128, &None, &false, &None);
+        client.close_campaign(&campaign_id, &creator);
+    }
+
+    #[test]
+    fn test_team_withdrawal_split() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let partner = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let title = String::from_str(&env, "Team Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        client.set_team(&campaign_id, &vec![
+            &env,
+            TeamMember { member: creator.clone(), share_bps: 7_000 },
+            TeamMember { member: partner.clone(), share_bps: 3_000 },
+        ]);
+        assert_eq!(client.get_team(&campaign_id).len(), 2);
+
+        // Co-creators fall under the creator donation rule
+        StellarAssetClient::new(&env, &asset).mint(&partner, &100);
+        assert!(client.try_donate(&campaign_id, &partner, &100i128, &None, &false, &None).is_err());
+
+        client.set_platform_fee(&100, &treasury);
+        StellarAssetClient::new(&env, &asset).mint(&donor, &1_000);
+        client.donate(&campaign_id, &donor, &1_000i128, &None, &false, &None);
+        client.withdraw(&campaign_id, &partner);
+
+        assert_eq!(token.balance(&creator), 693i128);
+        assert_eq!(token.balance(&partner), 397i128);
+        assert_eq!(client.get_accrued_fees(&asset), 10i128);
+    }
+
+    #[test]
+    fn test_team_change_requires_member_consent() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let partner = Address::generate(&env);
+        let title = String::from_str(&env, "Team Campaign");
+
+        env.mock_all_auths();
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        let team = vec![
+            &env,
+            TeamMember { member: creator.clone(), share_bps: 5_000 },
+            TeamMember { member: partner.clone(), share_bps: 5_000 },
+        ];
+        client.set_team(&campaign_id, &team);
+
+        // Dropping the partner needs the partner's signature too
+        client.set_team(&campaign_id, &vec![&env, TeamMember { member: creator.clone(), share_bps: 10_000 }]);
+        assert!(env.auths().iter().any(|(address, _)| *address == partner));
+
+        // Shares must add up to the full payout
+        let uneven = vec![&env, TeamMember { member: creator.clone(), share_bps: 9_000 }];
+        assert!(client.try_set_team(&campaign_id, &uneven).is_err());
+
+        // A donor to the campaign cannot become a payee of it
+        let donor = Address::generate(&env);
+        client.donate(&campaign_id, &donor, &100i128, &None, &true, &None);
+        let with_donor = vec![
+            &env,
+            TeamMember { member: creator.clone(), share_bps: 5_000 },
+            TeamMember { member: donor.clone(), share_bps: 5_000 },
+        ];
+        assert!(client.try_set_team(&campaign_id, &with_donor).is_err());
+    }
+
+    #[test]
+    fn test_multisig_withdrawal() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signer3 = Address::generate(&env);
+        let title = String::from_str(&env, "Multisig Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        client.set_withdrawal_signers(
+            &campaign_id,
+            &creator,
+            &vec![&env, signer1.clone(), signer2.clone(), signer3.clone()],
+            &2,
+        );
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &1_000);
+        client.donate(&campaign_id, &donor, &1_000i128, &None, &false, &None);
+
+        // Single-key withdrawal is disabled
+        assert!(client.try_withdraw(&campaign_id, &creator).is_err());
+
+        let proposal_id = client.propose_withdrawal(&campaign_id, &signer1);
+        assert!(client.try_execute_withdrawal(&proposal_id, &signer1).is_err());
+
+        client.approve_withdrawal(&proposal_id, &signer3);
+        client.execute_withdrawal(&proposal_id, &signer2);
+
+        assert_eq!(token.balance(&creator), 1_000i128);
+        assert!(client.get_withdrawal_proposal(&proposal_id).unwrap().executed);
+        assert!(client.try_execute_withdrawal(&proposal_id, &signer2).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Withdrawal proposal expired")]
+    fn test_stale_withdrawal_proposal() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let title = String::from_str(&env, "Multisig Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        client.set_withdrawal_signers(&campaign_id, &creator, &vec![&env, signer1.clone(), signer2.clone()], &2);
+        StellarAssetClient::new(&env, &asset).mint(&donor, &1_000);
+        client.donate(&campaign_id, &donor, &1_000i128, &None, &false, &None);
+
+        let proposal_id = client.propose_withdrawal(&campaign_id, &signer1);
+        env.ledger().set_timestamp(WITHDRAWAL_PROPOSAL_TTL + 1);
+        client.approve_withdrawal(&proposal_id, &signer2);
+    }
+
+    #[test]
+    fn test_streaming_payout() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Stream Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.set_payout_stream(&campaign_id, &creator, &1_000);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &1_000);
+        client.donate(&campaign_id, &donor, &1_000i128, &None, &false, &None);
+        assert!(client.try_withdraw(&campaign_id, &creator).is_err());
+
+        env.ledger().set_timestamp(250);
+        assert_eq!(client.claimable(&campaign_id).get(asset.clone()), Some(250i128));
+        client.claim_stream(&campaign_id);
+        assert_eq!(token.balance(&creator), 250i128);
+        assert_eq!(client.claimable(&campaign_id).get(asset.clone()), Some(0i128));
+
+        env.ledger().set_timestamp(5_000);
+        client.claim_stream(&campaign_id);
+        assert_eq!(token.balance(&creator), 1_000i128);
+        assert!(client.try_claim_stream(&campaign_id).is_err());
     }
 
-    /// Get campaigns by creator
-    pub fn get_campaigns_by_creator(env: Env, creator: Address) -> Vec<Campaign> {
-        let all_campaigns = Self::get_all_campaigns(env.clone());
-        let mut creator_campaigns = Vec::new(&env);
-        
-        for campaign in all_campaigns.iter() {
-            if campaign.creator == creator {
-                creator_campaigns.push_back(campaign);
-            }
-        }
-        creator_campaigns
+    #[test]
+    fn test_suspended_stream_refunds_remainder() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor1 = Address::generate(&env);
+        let donor2 = Address::generate(&env);
+        let title = String::from_str(&env, "Stream Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.set_payout_stream(&campaign_id, &creator, &1_000);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor1, &600);
+        StellarAssetClient::new(&env, &asset).mint(&donor2, &400);
+        client.donate(&campaign_id, &donor1, &600i128, &None, &false, &None);
+        client.donate(&campaign_id, &donor2, &400i128, &None, &false, &None);
+
+        env.ledger().set_timestamp(400);
+        client.suspend_campaign(&campaign_id);
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().status, CampaignStatus::Suspended);
+
+        // Donors recover the 60% that had not streamed yet
+        assert_eq!(client.claim_refund(&campaign_id, &donor1), 360i128);
+        assert_eq!(client.claim_refund(&campaign_id, &donor2), 240i128);
+        assert_eq!(token.balance(&donor1), 360i128);
+
+        // The team keeps what streamed before the suspension
+        env.ledger().set_timestamp(5_000);
+        client.claim_stream(&campaign_id);
+        assert_eq!(token.balance(&creator), 400i128);
+        assert_eq!(client.get_creator_stats(&creator).campaigns_suspended, 1);
     }
 
-    /// Get donations for a campaign
-    pub fn get_donations_by_campaign(env: Env, campaign_id: u64) -> Vec<Donation> {
-        env.storage().instance()
-            .get(&DataKey::Donations(campaign_id))
-            .unwrap_or(Vec::new(&env))
+    #[test]
+    fn test_dispute_freezes_until_released() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor1 = Address::generate(&env);
+        let donor2 = Address::generate(&env);
+        let arbiter = Address::generate(&env);
+        let title = String::from_str(&env, "Disputed Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        client.add_arbiter(&arbiter);
+        client.set_dispute_threshold(&2_500);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor1, &200);
+        StellarAssetClient::new(&env, &asset).mint(&donor2, &800);
+        client.donate(&campaign_id, &donor1, &200i128, &None, &false, &None);
+        client.donate(&campaign_id, &donor2, &800i128, &None, &false, &None);
+
+        // 20% support is below the 25% threshold
+        client.raise_dispute(&campaign_id, &donor1);
+        assert_eq!(client.get_dispute(&campaign_id).unwrap().status, DisputeStatus::Gathering);
+        client.raise_dispute(&campaign_id, &donor2);
+        assert_eq!(client.get_dispute(&campaign_id).unwrap().status, DisputeStatus::Open);
+
+        assert!(client.try_withdraw(&campaign_id, &creator).is_err());
+        assert!(client.try_resolve_dispute(&campaign_id, &donor1, &false).is_err());
+
+        client.resolve_dispute(&campaign_id, &arbiter, &false);
+        client.withdraw(&campaign_id, &creator);
+        assert_eq!(token.balance(&creator), 1_000i128);
     }
 
-    /// Get campaigns a wallet donated to
-    pub fn get_donations_by_wallet(env: Env, wallet: Address) -> Vec<u64> {
-        env.storage().instance()
-            .get(&DataKey::WalletDonations(wallet))
-            .unwrap_or(Vec::new(&env))
+    #[test]
+    fn test_dispute_opened_before_funding() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor1 = Address::generate(&env);
+        let donor2 = Address::generate(&env);
+        let arbiter = Address::generate(&env);
+        let title = String::from_str(&env, "Disputed Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.add_arbiter(&arbiter);
+        client.set_dispute_threshold(&2_000);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor1, &200);
+        StellarAssetClient::new(&env, &asset).mint(&donor2, &800);
+        client.donate(&campaign_id, &donor1, &200i128, &None, &false, &None);
+
+        // 20% of the target opens the dispute while the campaign is still open
+        client.raise_dispute(&campaign_id, &donor1);
+        assert_eq!(client.get_dispute(&campaign_id).unwrap().status, DisputeStatus::Open);
+        assert!(client.try_retract_donation(&campaign_id, &donor1, &100i128).is_err());
+
+        // The funding donation cannot be withdrawn while the dispute is open
+        client.donate(&campaign_id, &donor2, &800i128, &None, &false, &None);
+        assert!(client.try_withdraw(&campaign_id, &creator).is_err());
+
+        client.resolve_dispute(&campaign_id, &arbiter, &true);
+        client.claim_refund(&campaign_id, &donor2);
+        assert_eq!(token.balance(&donor2), 800i128);
     }
 
-    /// Get campaign count
-    pub fn get_campaign_count(env: Env) -> u64 {
-        env.storage().instance()
-            .get(&DataKey::CampaignCounter)
-            .unwrap_or(0)
+    #[test]
+    fn test_dispute_timeout_refunds_donors() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor1 = Address::generate(&env);
+        let donor2 = Address::generate(&env);
+        let arbiter = Address::generate(&env);
+        let title = String::from_str(&env, "Disputed Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.add_arbiter(&arbiter);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor1, &300);
+        StellarAssetClient::new(&env, &asset).mint(&donor2, &700);
+        client.donate(&campaign_id, &donor1, &300i128, &None, &false, &None);
+        client.donate(&campaign_id, &donor2, &700i128, &None, &false, &None);
+        client.raise_dispute(&campaign_id, &donor1);
+
+        // Arbiters cannot act late and the deadline must pass before a timeout
+        assert!(client.try_timeout_dispute(&campaign_id).is_err());
+        env.ledger().set_timestamp(DISPUTE_TIMEOUT + 1);
+        assert!(client.try_resolve_dispute(&campaign_id, &arbiter, &false).is_err());
+
+        client.timeout_dispute(&campaign_id);
+        assert_eq!(client.get_dispute(&campaign_id).unwrap().status, DisputeStatus::Refunded);
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().status, CampaignStatus::Suspended);
+
+        assert_eq!(client.claim_refund(&campaign_id, &donor2), 700i128);
+        assert_eq!(token.balance(&donor2), 700i128);
+        assert!(client.try_withdraw(&campaign_id, &creator).is_err());
     }
-}
 
-/// Test suite
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
+    #[test]
+    fn test_verification_attestations() {
+        let env = Env::default();
+        let (client, _) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let title = String::from_str(&env, "Verified Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        let kyc = AttestationSubject::Creator(creator.clone());
+        assert!(client.try_attest(&verifier, &kyc, &1_000).is_err());
+
+        client.add_verifier(&verifier);
+        client.attest(&verifier, &kyc, &1_000);
+        client.attest(&verifier, &AttestationSubject::Charity(campaign_id), &5_000);
+
+        let verification = client.get_campaign_verification(&campaign_id);
+        assert!(verification.creator_kyc && verification.registered_charity);
+
+        // KYC lapses at expiry; charity status ends on revocation
+        env.ledger().set_timestamp(1_000);
+        assert!(!client.get_campaign_verification(&campaign_id).creator_kyc);
+        client.revoke_attestation(&verifier, &AttestationSubject::Charity(campaign_id));
+        assert!(!client.get_campaign_verification(&campaign_id).registered_charity);
+        assert!(client.get_attestation(&AttestationSubject::Charity(campaign_id)).unwrap().revoked);
+    }
 
     #[test]
-    fn test_create_campaign() {
+    fn test_attestations_belong_to_their_verifier() {
+        let env = Env::default();
+        let (client, _) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let verifier1 = Address::generate(&env);
+        let verifier2 = Address::generate(&env);
+        let title = String::from_str(&env, "Verified Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.add_verifier(&verifier1);
+        client.add_verifier(&verifier2);
+
+        let kyc = AttestationSubject::Creator(creator.clone());
+        client.attest(&verifier1, &kyc, &1_000);
+
+        // Another verifier can neither replace nor revoke a live attestation
+        assert!(client.try_attest(&verifier2, &kyc, &2_000).is_err());
+        assert!(client.try_revoke_attestation(&verifier2, &kyc).is_err());
+
+        // Removing the issuer voids its attestation and frees the subject
+        client.remove_verifier(&verifier1);
+        assert!(!client.get_campaign_verification(&campaign_id).creator_kyc);
+        client.attest(&verifier2, &kyc, &2_000);
+        assert!(client.get_campaign_verification(&campaign_id).creator_kyc);
+
+        // The admin can revoke any attestation
+        client.revoke_attestation(&client.get_admin().unwrap(), &kyc);
+        assert!(!client.get_campaign_verification(&campaign_id).creator_kyc);
+    }
+
+    #[test]
+    fn test_large_donation_requires_acknowledgement() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let title = String::from_str(&env, "Verified Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &10_000i128, &30);
+        client.set_ack_threshold(&campaign_id, &creator, &500i128);
+        client.add_verifier(&verifier);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &2_000);
+        client.donate(&campaign_id, &donor, &100i128, &None, &false, &None);
+        assert!(client.try_donate(&campaign_id, &donor, &500i128, &None, &false, &None).is_err());
+
+        client.acknowledge_verification(&campaign_id, &donor);
+        client.donate(&campaign_id, &donor, &500i128, &None, &false, &None);
+
+        // A status change invalidates the earlier acknowledgement
+        client.attest(&verifier, &AttestationSubject::Creator(creator.clone()), &1_000);
+        assert!(client.try_donate(&campaign_id, &donor, &500i128, &None, &false, &None).is_err());
+        assert!(client.acknowledge_verification(&campaign_id, &donor).creator_kyc);
+        client.donate(&campaign_id, &donor, &500i128, &None, &false, &None);
+    }
+
+    #[test]
+    fn test_creation_bond_refunded_after_clean_end() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Bonded Campaign");
+        client.set_creation_bond(&Some(BondConfig { asset: asset.clone(), amount: 50 }));
+
+        StellarAssetClient::new(&env, &asset).mint(&creator, &50);
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        assert_eq!(token.balance(&creator), 0);
+        assert_eq!(client.get_campaign_bond(&campaign_id).unwrap().status, BondStatus::Held);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &1_000);
+        client.donate(&campaign_id, &donor, &1_000i128, &None, &false, &None);
+
+        // Funds must be paid out before the bond comes back
+        assert!(client.try_reclaim_bond(&campaign_id, &creator).is_err());
+        client.withdraw(&campaign_id, &creator);
+        assert_eq!(client.reclaim_bond(&campaign_id, &creator), 50i128);
+        assert_eq!(token.balance(&creator), 1_050i128);
+        assert!(client.try_reclaim_bond(&campaign_id, &creator).is_err());
+
+        // Closing before any donation is a clean end; closing after one is not
+        let unused = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.close_campaign(&unused, &creator);
+        assert_eq!(client.reclaim_bond(&unused, &creator), 50i128);
+
+        let abandoned = client.create_campaign(&creator, &title, &1_000i128, &30);
+        StellarAssetClient::new(&env, &asset).mint(&donor, &100);
+        client.donate(&abandoned, &donor, &100i128, &None, &false, &None);
+        client.close_campaign(&abandoned, &creator);
+        assert!(client.try_reclaim_bond(&abandoned, &creator).is_err());
+    }
+
+    #[test]
+    fn test_disputed_campaign_keeps_bond_after_expiry() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Bonded Campaign");
+        client.set_creation_bond(&Some(BondConfig { asset: asset.clone(), amount: 100 }));
+        client.add_arbiter(&Address::generate(&env));
+
+        StellarAssetClient::new(&env, &asset).mint(&creator, &100);
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &1);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &200);
+        client.donate(&campaign_id, &donor, &200i128, &None, &false, &None);
+        client.raise_dispute(&campaign_id, &donor);
+
+        // Letting the campaign expire does not dodge the pending dispute
+        env.ledger().set_timestamp(86_401);
+        assert!(client.check_expired(&campaign_id));
+        assert!(client.try_reclaim_bond(&campaign_id, &creator).is_err());
+        assert_eq!(client.get_campaign_bond(&campaign_id).unwrap().status, BondStatus::Held);
+    }
+
+    #[test]
+    fn test_dispute_slashes_bond_for_donors() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor1 = Address::generate(&env);
+        let donor2 = Address::generate(&env);
+        let title = String::from_str(&env, "Bonded Campaign");
+        client.set_creation_bond(&Some(BondConfig { asset: asset.clone(), amount: 100 }));
+
+        StellarAssetClient::new(&env, &asset).mint(&creator, &100);
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor1, &250);
+        StellarAssetClient::new(&env, &asset).mint(&donor2, &750);
+        client.donate(&campaign_id, &donor1, &250i128, &None, &false, &None);
+        client.donate(&campaign_id, &donor2, &750i128, &None, &false, &None);
+
+        // Disputes need someone to arbitrate them
+        assert!(client.try_raise_dispute(&campaign_id, &donor1).is_err());
+        client.add_arbiter(&Address::generate(&env));
+
+        client.raise_dispute(&campaign_id, &donor1);
+        env.ledger().set_timestamp(DISPUTE_TIMEOUT + 1);
+        client.timeout_dispute(&campaign_id);
+
+        assert_eq!(client.get_campaign_bond(&campaign_id).unwrap().status, BondStatus::Slashed);
+        assert!(client.try_reclaim_bond(&campaign_id, &creator).is_err());
+
+        assert_eq!(client.claim_bond_compensation(&campaign_id, &donor1), 25i128);
+        assert_eq!(client.claim_bond_compensation(&campaign_id, &donor2), 75i128);
+        client.claim_refund(&campaign_id, &donor2);
+        assert_eq!(token.balance(&donor2), 825i128);
+    }
+
+    #[test]
+    fn test_private_campaign_allowlist() {
         let env = Env::default();
         let contract_id = env.register_contract(None, CrowdfundingContract);
         let client = CrowdfundingContractClient::new(&env, &contract_id);
 
         let creator = Address::generate(&env);
-        let title = String::from_str(&env, "Test Campaign");
-        let target = 1_000_000_000i128;
+        let member = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        let title = String::from_str(&env, "Internal Fundraiser");
 
         env.mock_all_auths();
-        let campaign_id = client.create_campaign(&creator, &title, &target, &30);
+        let public_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        let private_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.set_campaign_private(&private_id, &creator, &true);
+        client.add_to_allowlist(&private_id, &creator, &vec![&env, member.clone()]);
 
-        assert_eq!(campaign_id, 1);
-        let campaign = client.get_campaign(&campaign_id).unwrap();
-        assert_eq!(campaign.status, CampaignStatus::Open);
+        // Hidden from the default listing
+        let listed = client.get_all_campaigns(&false);
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed.get(0).unwrap().id, public_id);
+        assert_eq!(client.get_all_campaigns(&true).len(), 2);
+        assert_eq!(client.get_campaigns_by_creator(&creator).len(), 2);
+
+        client.donate(&private_id, &member, &100i128, &None, &false, &None);
+        assert!(client.try_donate(&private_id, &outsider, &100i128, &None, &false, &None).is_err());
+        assert!(client.try_donate_for(&private_id, &outsider, &member, &100i128, &None).is_err());
+
+        client.remove_from_allowlist(&private_id, &creator, &vec![&env, member.clone()]);
+        assert!(!client.is_allowlisted(&private_id, &member));
+        assert!(client.try_donate(&private_id, &member, &100i128, &None, &false, &None).is_err());
     }
 
     #[test]
-    #[should_panic(expected = "Creator cannot donate to their own campaign")]
-    fn test_creator_cannot_donate() {
+    fn test_private_campaign_merkle_allowlist() {
         let env = Env::default();
         let contract_id = env.register_contract(None, CrowdfundingContract);
         let client = CrowdfundingContractClient::new(&env, &contract_id);
 
         let creator = Address::generate(&env);
-        let title = String::from_str(&env, "Test Campaign");
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        let title = String::from_str(&env, "Internal Fundraiser");
 
         env.mock_all_auths();
-        let campaign_id = client.create_campaign(&creator, &title, &1_000_000_000i128, &30);
-        client.donate(&campaign_id, &creator, &100_000_000i128);
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.set_campaign_private(&campaign_id, &creator, &true);
+
+        let leaf1: BytesN<32> = env.crypto().sha256(&member1.clone().to_xdr(&env)).into();
+        let leaf2: BytesN<32> = env.crypto().sha256(&member2.clone().to_xdr(&env)).into();
+        let (first, second) = if leaf1 < leaf2 { (&leaf1, &leaf2) } else { (&leaf2, &leaf1) };
+        let mut pair = Bytes::from(first.clone());
+        pair.append(&Bytes::from(second.clone()));
+        let root: BytesN<32> = env.crypto().sha256(&pair).into();
+        client.set_allowlist_root(&campaign_id, &creator, &Some(root));
+
+        client.prove_allowlist(&campaign_id, &member1, &vec![&env, leaf2.clone()]);
+        client.donate(&campaign_id, &member1, &100i128, &None, &false, &None);
+
+        assert!(client.try_prove_allowlist(&campaign_id, &outsider, &vec![&env, leaf2]).is_err());
+        assert!(!client.is_allowlisted(&campaign_id, &outsider));
     }
 
     #[test]
-    fn test_multi_wallet_donations() {
+    #[should_panic(expected = "Campaign is not funded")]
+    fn test_withdraw_before_funded() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Fee Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &500);
+        client.donate(&campaign_id, &donor, &500i128, &None, &false, &None);
+        client.withdraw(&campaign_id, &creator);
+    }
+
+    #[test]
+    #[should_panic(expected = "Fee exceeds maximum")]
+    fn test_platform_fee_cap() {
+        let env = Env::default();
+        let (client, _) = setup_native_asset(&env);
+
+        let treasury = Address::generate(&env);
+        client.set_platform_fee(&1_001, &treasury);
+    }
+
+    /// Register the real SST contract and wire it to the crowdfunding contract
+    fn setup_reward_token<'a>(
+        env: &Env,
+        client: &CrowdfundingContractClient<'a>,
+    ) -> reward_token::RewardTokenContractClient<'a> {
+        setup_capped_reward_token(env, client, None)
+    }
+
+    /// Register the real SST contract with an optional supply cap
+    fn setup_capped_reward_token<'a>(
+        env: &Env,
+        client: &CrowdfundingContractClient<'a>,
+        supply_cap: Option<i128>,
+    ) -> reward_token::RewardTokenContractClient<'a> {
+        let token_id = env.register_contract(None, reward_token::RewardTokenContract);
+        let token = reward_token::RewardTokenContractClient::new(env, &token_id);
+
+        env.mock_all_auths();
+        token.initialize(
+            &client.address,
+            &String::from_str(env, "Stellar Support Token"),
+            &String::from_str(env, "SST"),
+            &7,
+            &supply_cap,
+        );
+        token.add_minter(&client.address, &1_000_000_000_000_000i128);
+        client.set_reward_token(&Address::generate(env), &token_id);
+
+        token
+    }
+
+    #[test]
+    fn test_retract_donation() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let sst = setup_reward_token(&env, &client);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Retract Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &500);
+        client.donate(&campaign_id, &donor, &200i128, &None, &false, &None);
+        client.donate(&campaign_id, &donor, &100i128, &None, &false, &None);
+        assert_eq!(sst.balance(&donor), 3_000i128);
+
+        // Removes the latest donation entirely and 50 of the first
+        client.retract_donation(&campaign_id, &donor, &150i128);
+
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().total_donated, 150i128);
+        assert_eq!(token.balance(&donor), 350i128);
+        assert_eq!(client.get_raised_by_asset(&campaign_id, &asset), 150i128);
+        assert_eq!(sst.balance(&donor), 1_500i128);
+
+        let donations = client.get_donations_by_campaign(&campaign_id);
+        assert_eq!(donations.len(), 1);
+        assert_eq!(donations.get(0).unwrap().amount, 150i128);
+        assert_eq!(client.get_donations_by_wallet(&donor), vec![&env, campaign_id]);
+
+        // SST already moved away does not block a retraction; it becomes debt
+        sst.transfer(&donor, &Address::generate(&env), &1_000i128);
+        client.retract_donation(&campaign_id, &donor, &150i128);
+        assert_eq!(token.balance(&donor), 500i128);
+        assert_eq!(sst.balance(&donor), 0);
+        assert_eq!(sst.debt(&donor), 1_000i128);
+    }
+
+    #[test]
+    fn test_large_donation_rewards_vest() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let sst = setup_reward_token(&env, &client);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Vesting Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &10_000i128, &30);
+
+        client.set_vesting_policy(&Some(VestingPolicy {
+            threshold: 1_000,
+            cliff_ledgers: 100,
+            duration_ledgers: 1_000,
+        }));
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &2_000);
+        client.donate(&campaign_id, &donor, &500i128, &None, &false, &None);
+        client.donate(&campaign_id, &donor, &1_000i128, &None, &false, &None);
+
+        // Small donation is liquid, large one is locked
+        assert_eq!(sst.balance(&donor), 5_000i128);
+        assert_eq!(sst.locked_balance(&donor), 10_000i128);
+        assert!(client.get_donations_by_campaign(&campaign_id).get(1).unwrap().vested);
+
+        // Retracting the large donation reclaims from the vesting position
+        client.retract_donation(&campaign_id, &donor, &400i128);
+        assert_eq!(sst.locked_balance(&donor), 6_000i128);
+        assert_eq!(sst.balance(&donor), 5_000i128);
+
+        env.ledger().set_sequence_number(env.ledger().sequence() + 1_000);
+        assert_eq!(sst.claim_vested(&donor), 6_000i128);
+        assert_eq!(sst.balance(&donor), 11_000i128);
+    }
+
+    #[test]
+    #[should_panic(expected = "Retraction exceeds eligible donations")]
+    fn test_retract_after_grace_period() {
         let env = Env::default();
         let contract_id = env.register_contract(None, CrowdfundingContract);
         let client = CrowdfundingContractClient::new(&env, &contract_id);
 
         let creator = Address::generate(&env);
-        let donor1 = Address::generate(&env);
-        let donor2 = Address::generate(&env);
-        let title = String::from_str(&env, "Test Campaign");
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Retract Campaign");
 
         env.mock_all_auths();
-        let campaign_id = client.create_campaign(&creator, &title, &1_000_000_000i128, &30);
-        client.donate(&campaign_id, &donor1, &200_000_000i128);
-        client.donate(&campaign_id, &donor2, &300_000_000i128);
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.donate(&campaign_id, &donor, &100i128, &None, &false, &None);
 
-        let campaign = client.get_campaign(&campaign_id).unwrap();
-        assert_eq!(campaign.total_donated, 500_000_000i128);
+        env.ledger().set_timestamp(RETRACT_GRACE_PERIOD + 1);
+        client.retract_donation(&campaign_id, &donor, &100i128);
     }
 
     #[test]
-    #[should_panic(expected = "Donation exceeds target. Overfunding not allowed")]
-    fn test_overfunding_prevention() {
+    #[should_panic(expected = "Campaign is not open")]
+    fn test_retract_cannot_undo_funded() {
         let env = Env::default();
         let contract_id = env.register_contract(None, CrowdfundingContract);
         let client = CrowdfundingContractClient::new(&env, &contract_id);
 
         let creator = Address::generate(&env);
         let donor = Address::generate(&env);
-        let title = String::from_str(&env, "Test Campaign");
+        let title = String::from_str(&env, "Retract Campaign");
 
         env.mock_all_auths();
-        let campaign_id = client.create_campaign(&creator, &title, &1_000_000_000i128, &30);
-        client.donate(&campaign_id, &donor, &1_500_000_000i128);
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.donate(&campaign_id, &donor, &1_000i128, &None, &false, &None);
+        client.retract_donation(&campaign_id, &donor, &100i128);
     }
 
     #[test]
-    #[should_panic(expected = "Campaign is not accepting donations")]
-    fn test_donation_after_close() {
+    fn test_refund_claws_back_sst() {
+        let env = Env::default();
+        let (client, asset) = setup_native_asset(&env);
+        let sst = setup_reward_token(&env, &client);
+        let token = TokenClient::new(&env, &asset);
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let friend = Address::generate(&env);
+        let title = String::from_str(&env, "Refund Campaign");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &1);
+
+        StellarAssetClient::new(&env, &asset).mint(&donor, &100);
+        client.donate(&campaign_id, &donor, &100i128, &None, &false, &None);
+        assert_eq!(sst.balance(&donor), 1_000i128);
+
+        // Donor moves part of the reward away before the refund
+        sst.transfer(&donor, &friend, &400i128);
+
+        env.ledger().set_timestamp(86_401);
+        assert_eq!(client.claim_refund(&campaign_id, &donor), 100i128);
+
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().status, CampaignStatus::Expired);
+        assert_eq!(token.balance(&donor), 100i128);
+        assert_eq!(sst.balance(&donor), 0i128);
+        assert_eq!(sst.debt(&donor), 400i128);
+        assert!(client.is_refunded(&campaign_id, &donor));
+
+        // Debt is settled by the next reward
+        let other = client.create_campaign(&creator, &title, &1_000i128, &30);
+        StellarAssetClient::new(&env, &asset).mint(&donor, &100);
+        client.donate(&other, &donor, &100i128, &None, &false, &None);
+        assert_eq!(sst.debt(&donor), 0i128);
+        assert_eq!(sst.balance(&donor), 600i128);
+    }
+
+    #[test]
+    #[should_panic(expected = "Campaign is not refundable")]
+    fn test_refund_open_campaign() {
         let env = Env::default();
         let contract_id = env.register_contract(None, CrowdfundingContract);
         let client = CrowdfundingContractClient::new(&env, &contract_id);
 
         let creator = Address::generate(&env);
         let donor = Address::generate(&env);
-        let title = String::from_str(&env, "Test Campaign");
+        let title = String::from_str(&env, "Refund Campaign");
 
         env.mock_all_auths();
-        let campaign_id = client.create_campaign(&creator, &title, &1_000_000_000i128, &30);
-        client.close_campaign(&campaign_id, &creator);
-        client.donate(&campaign_id, &donor, &100_000_000i128);
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+        client.donate(&campaign_id, &donor, &100i128, &None, &false, &None);
+        client.claim_refund(&campaign_id, &donor);
     }
 
     #[test]
-    fn test_auto_funded_status() {
+    fn test_reward_rate_and_pause() {
         let env = Env::default();
         let contract_id = env.register_contract(None, CrowdfundingContract);
         let client = CrowdfundingContractClient::new(&env, &contract_id);
+        let sst = setup_reward_token(&env, &client);
 
+        let admin = Address::generate(&env);
         let creator = Address::generate(&env);
         let donor = Address::generate(&env);
-        let title = String::from_str(&env, "Test Campaign");
-        let target = 1_000_000_000i128;
+        let title = String::from_str(&env, "Rate Campaign");
+
+        client.set_admin(&admin);
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        client.set_reward_rate(&3);
+        client.donate(&campaign_id, &donor, &100i128, &None, &false, &None);
+        assert_eq!(sst.balance(&donor), 300i128);
+
+        // Burns use the rate recorded at donation time
+        client.set_reward_rate(&20);
+        client.retract_donation(&campaign_id, &donor, &50i128);
+        assert_eq!(sst.balance(&donor), 150i128);
+
+        client.set_paused(&true);
+        assert!(client.is_paused());
+        assert!(client.try_donate(&campaign_id, &donor, &10i128, &None, &false, &None).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only curators can feature campaigns")]
+    fn test_feature_requires_curator() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let curator = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let title = String::from_str(&env, "Featured Campaign");
 
         env.mock_all_auths();
-        let campaign_id = client.create_campaign(&creator, &title, &target, &30);
-        client.donate(&campaign_id, &donor, &target);
+        client.set_admin(&admin);
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
 
-        let campaign = client.get_campaign(&campaign_id).unwrap();
-        assert_eq!(campaign.status, CampaignStatus::Funded);
+        client.add_curator(&curator);
+        client.set_featured(&curator, &campaign_id, &true);
+        assert_eq!(client.get_featured_campaigns(), vec![&env, campaign_id]);
+
+        client.remove_curator(&curator);
+        client.set_featured(&curator, &campaign_id, &false);
+    }
+
+    #[test]
+    fn test_donation_survives_sst_cap() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CrowdfundingContract);
+        let client = CrowdfundingContractClient::new(&env, &contract_id);
+        let sst = setup_capped_reward_token(&env, &client, Some(1_000i128));
+
+        let creator = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let title = String::from_str(&env, "Capped Rewards");
+        let campaign_id = client.create_campaign(&creator, &title, &1_000i128, &30);
+
+        client.donate(&campaign_id, &donor, &60i128, &None, &false, &None);
+        client.donate(&campaign_id, &donor, &100i128, &None, &false, &None);
+        client.donate(&campaign_id, &donor, &100i128, &None, &false, &None);
+
+        // The reward crossing the cap is clamped; past the cap donations go unrewarded
+        let donations = client.get_donations_by_campaign(&campaign_id);
+        assert_eq!(sst.balance(&donor), 1_000i128);
+        assert_eq!(client.get_campaign(&campaign_id).unwrap().total_donated, 260i128);
+        assert_eq!(donations.get(1).unwrap().reward, 400i128);
+        assert_eq!(donations.get(2).unwrap().reward, 0i128);
+
+        // Retraction burns only what was credited
+        client.retract_donation(&campaign_id, &donor, &200i128);
+        assert_eq!(sst.balance(&donor), 600i128);
     }
 }
@@ -0,0 +1,532 @@
+//! SST Governance
+//!
+//! Community control over the Stellar Campaign Hub platform parameters.
+//! Deployed as the admin of the CrowdfundingContract so that passed
+//! proposals can call its admin-only entrypoints.
+//!
+//! Features:
+//! - Proposals restricted to a fixed set of admin actions
+//! - Voting with staked SST power snapshotted at proposal creation
+//! - Quorum relative to total staked SST
+//! - Timelock between a successful vote and execution
+//! - Structured event emission
+
+#![no_std]
+use soroban_sdk::{
+    contract, contractimpl, contracttype, contractclient, symbol_short,
+    Address, Env, log,
+};
+
+/// Ledgers after the timelock during which a passed proposal can be executed
+const EXECUTION_WINDOW: u32 = 17_280;
+
+/// External SST contract client for voting power lookups
+#[contractclient(name = "VotingTokenClient")]
+pub trait VotingTokenInterface {
+    fn voting_power(env: Env, account: Address, at_ledger: u32) -> i128;
+    fn staked_at(env: Env, account: Address, at_ledger: u32) -> i128;
+    fn total_staked_at(env: Env, at_ledger: u32) -> i128;
+}
+
+/// External CrowdfundingContract client for admin-only entrypoints
+#[contractclient(name = "CrowdfundingAdminClient")]
+pub trait CrowdfundingAdminInterface {
+    fn set_platform_fee(env: Env, fee_bps: u32, treasury: Address);
+    fn set_reward_rate(env: Env, rate: i128);
+    fn set_paused(env: Env, paused: bool);
+    fn add_curator(env: Env, curator: Address);
+    fn remove_curator(env: Env, curator: Address);
+    fn withdraw_fees(env: Env, asset: Address) -> i128;
+    fn transfer_admin(env: Env, new_admin: Address);
+    fn suspend_campaign(env: Env, campaign_id: u64);
+    fn slash_bond(env: Env, campaign_id: u64);
+    fn add_arbiter(env: Env, arbiter: Address);
+    fn remove_arbiter(env: Env, arbiter: Address);
+    fn add_verifier(env: Env, verifier: Address);
+    fn remove_verifier(env: Env, verifier: Address);
+    fn set_dispute_threshold(env: Env, threshold_bps: u32);
+    fn set_price_oracle(env: Env, oracle: Address, max_age: u64);
+    fn set_native_asset(env: Env, asset: Address);
+    fn set_creation_bond(env: Env, config: Option<BondConfig>);
+    fn set_vesting_policy(env: Env, policy: Option<VestingPolicy>);
+}
+
+/// Creation bond settings, mirroring the CrowdfundingContract type
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct BondConfig {
+    pub asset: Address,
+    pub amount: i128,
+}
+
+/// Reward vesting settings, mirroring the CrowdfundingContract type
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct VestingPolicy {
+    pub threshold: i128,
+    pub cliff_ledgers: u32,
+    pub duration_ledgers: u32,
+}
+
+/// Admin action a proposal executes on the CrowdfundingContract
+/// Covers every admin-only entrypoint, since governance holds the admin role
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum ProposalAction {
+    SetPlatformFee(u32, Address),
+    SetRewardRate(i128),
+    SetPaused(bool),
+    AddCurator(Address),
+    RemoveCurator(Address),
+    WithdrawFees(Address),
+    TransferAdmin(Address), // Hand the platform to a new admin or governance contract
+    SuspendCampaign(u64),
+    SlashBond(u64),
+    AddArbiter(Address),
+    RemoveArbiter(Address),
+    AddVerifier(Address),
+    RemoveVerifier(Address),
+    SetDisputeThreshold(u32),
+    SetPriceOracle(Address, u64),
+    SetNativeAsset(Address),
+    SetCreationBond(BondConfig),
+    ClearCreationBond,
+    SetVestingPolicy(VestingPolicy),
+    ClearVestingPolicy,
+}
+
+/// Proposal lifecycle state
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ProposalState {
+    Active,     // Voting open
+    Defeated,   // Quorum missed or more votes against
+    Queued,     // Passed, waiting for the timelock
+    Executable, // Timelock elapsed
+    Executed,
+    Expired,    // Passed but not executed in time
+}
+
+/// Governance parameters
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct GovernanceConfig {
+    pub token: Address,          // SST contract
+    pub target: Address,         // CrowdfundingContract
+    pub quorum_bps: u32,         // Share of total staked SST that must vote
+    pub voting_period: u32,      // Ledgers
+    pub timelock: u32,           // Ledgers between vote end and execution
+    pub proposal_threshold: i128, // Voting power needed to propose
+}
+
+/// Proposal data structure
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub action: ProposalAction,
+    pub snapshot_ledger: u32,
+    pub vote_end_ledger: u32,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub turnout: i128, // Staked SST of voters, without the lock bonus
+    pub executed: bool,
+}
+
+/// Storage keys
+#[contracttype]
+pub enum GovDataKey {
+    Config,
+    Proposal(u64),
+    ProposalCounter,
+    Voted(u64, Address),
+}
+
+#[contract]
+pub struct GovernanceContract;
+
+#[contractimpl]
+impl GovernanceContract {
+    /// Initialize governance (one-time setup)
+    pub fn initialize(
+        env: Env,
+        token: Address,
+        target: Address,
+        quorum_bps: u32,
+        voting_period: u32,
+        timelock: u32,
+        proposal_threshold: i128,
+    ) {
+        if env.storage().instance().has(&GovDataKey::Config) {
+            panic!("Already initialized");
+        }
+
+        if quorum_bps > 10_000 {
+            panic!("Quorum exceeds 100%");
+        }
+
+        if voting_period == 0 {
+            panic!("Voting period must be positive");
+        }
+
+        let config = GovernanceConfig {
+            token,
+            target,
+            quorum_bps,
+            voting_period,
+            timelock,
+            proposal_threshold,
+        };
+        env.storage().instance().set(&GovDataKey::Config, &config);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("GOV"), symbol_short!("init")),
+            (config.target, quorum_bps, voting_period, timelock),
+        );
+
+        log!(&env, "Governance initialized");
+    }
+
+    /// Create a proposal; voting power is snapshotted at the previous ledger
+    pub fn propose(env: Env, proposer: Address, action: ProposalAction) -> u64 {
+        proposer.require_auth();
+
+        let config = load_config(&env);
+        let current = env.ledger().sequence();
+        let snapshot_ledger = current.saturating_sub(1);
+
+        let power = VotingTokenClient::new(&env, &config.token)
+            .voting_power(&proposer, &snapshot_ledger);
+        if power < config.proposal_threshold {
+            panic!("Insufficient voting power to propose");
+        }
+
+        let proposal_id: u64 = env.storage().instance()
+            .get(&GovDataKey::ProposalCounter)
+            .unwrap_or(0) + 1;
+        env.storage().instance().set(&GovDataKey::ProposalCounter, &proposal_id);
+
+        let proposal = Proposal {
+            id: proposal_id,
+            proposer: proposer.clone(),
+            action,
+            snapshot_ledger,
+            vote_end_ledger: current + config.voting_period,
+            votes_for: 0,
+            votes_against: 0,
+            turnout: 0,
+            executed: false,
+        };
+        env.storage().instance().set(&GovDataKey::Proposal(proposal_id), &proposal);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("GOV"), symbol_short!("proposed")),
+            (proposal_id, proposer, proposal.vote_end_ledger),
+        );
+
+        proposal_id
+    }
+
+    /// Vote on an active proposal with snapshotted voting power
+    pub fn vote(env: Env, voter: Address, proposal_id: u64, support: bool) -> i128 {
+        voter.require_auth();
+
+        let config = load_config(&env);
+        let mut proposal = load_proposal(&env, proposal_id);
+
+        if env.ledger().sequence() > proposal.vote_end_ledger {
+            panic!("Voting has ended");
+        }
+
+        let voted_key = GovDataKey::Voted(proposal_id, voter.clone());
+        if env.storage().instance().has(&voted_key) {
+            panic!("Already voted");
+        }
+
+        let token = VotingTokenClient::new(&env, &config.token);
+        let power = token.voting_power(&voter, &proposal.snapshot_ledger);
+        if power <= 0 {
+            panic!("No voting power");
+        }
+
+        proposal.turnout += token.staked_at(&voter, &proposal.snapshot_ledger);
+        if support {
+            proposal.votes_for += power;
+        } else {
+            proposal.votes_against += power;
+        }
+
+        env.storage().instance().set(&voted_key, &support);
+        env.storage().instance().set(&GovDataKey::Proposal(proposal_id), &proposal);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("GOV"), symbol_short!("voted")),
+            (proposal_id, voter, support, power),
+        );
+
+        power
+    }
+
+    /// Execute a passed proposal once its timelock has elapsed (callable by anyone)
+    pub fn execute(env: Env, proposal_id: u64) {
+        let config = load_config(&env);
+        let mut proposal = load_proposal(&env, proposal_id);
+
+        match proposal_state(&env, &config, &proposal) {
+            ProposalState::Executable => {}
+            ProposalState::Queued => panic!("Timelock has not elapsed"),
+            ProposalState::Executed => panic!("Proposal already executed"),
+            _ => panic!("Proposal cannot be executed"),
+        }
+
+        proposal.executed = true;
+        env.storage().instance().set(&GovDataKey::Proposal(proposal_id), &proposal);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        // Inter-contract call: this contract is the CrowdfundingContract admin
+        let target = CrowdfundingAdminClient::new(&env, &config.target);
+        match proposal.action.clone() {
+            ProposalAction::SetPlatformFee(fee_bps, treasury) => target.set_platform_fee(&fee_bps, &treasury),
+            ProposalAction::SetRewardRate(rate) => target.set_reward_rate(&rate),
+            ProposalAction::SetPaused(paused) => target.set_paused(&paused),
+            ProposalAction::AddCurator(curator) => target.add_curator(&curator),
+            ProposalAction::RemoveCurator(curator) => target.remove_curator(&curator),
+            ProposalAction::WithdrawFees(asset) => {
+                target.withdraw_fees(&asset);
+            }
+            ProposalAction::TransferAdmin(new_admin) => target.transfer_admin(&new_admin),
+            ProposalAction::SuspendCampaign(campaign_id) => target.suspend_campaign(&campaign_id),
+            ProposalAction::SlashBond(campaign_id) => target.slash_bond(&campaign_id),
+            ProposalAction::AddArbiter(arbiter) => target.add_arbiter(&arbiter),
+            ProposalAction::RemoveArbiter(arbiter) => target.remove_arbiter(&arbiter),
+            ProposalAction::AddVerifier(verifier) => target.add_verifier(&verifier),
+            ProposalAction::RemoveVerifier(verifier) => target.remove_verifier(&verifier),
+            ProposalAction::SetDisputeThreshold(threshold_bps) => target.set_dispute_threshold(&threshold_bps),
+            ProposalAction::SetPriceOracle(oracle, max_age) => target.set_price_oracle(&oracle, &max_age),
+            ProposalAction::SetNativeAsset(asset) => target.set_native_asset(&asset),
+            ProposalAction::SetCreationBond(config) => target.set_creation_bond(&Some(config)),
+            ProposalAction::ClearCreationBond => target.set_creation_bond(&None),
+            ProposalAction::SetVestingPolicy(policy) => target.set_vesting_policy(&Some(policy)),
+            ProposalAction::ClearVestingPolicy => target.set_vesting_policy(&None),
+        }
+
+        env.events().publish(
+            (symbol_short!("GOV"), symbol_short!("executed")),
+            (proposal_id, proposal.action),
+        );
+    }
+
+    /// Get the current state of a proposal
+    pub fn state(env: Env, proposal_id: u64) -> ProposalState {
+        let config = load_config(&env);
+        let proposal = load_proposal(&env, proposal_id);
+        proposal_state(&env, &config, &proposal)
+    }
+
+    /// Get proposal by ID
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Option<Proposal> {
+        env.storage().instance().get(&GovDataKey::Proposal(proposal_id))
+    }
+
+    /// Get governance parameters
+    pub fn get_config(env: Env) -> GovernanceConfig {
+        load_config(&env)
+    }
+}
+
+/// Load governance parameters or panic
+fn load_config(env: &Env) -> GovernanceConfig {
+    env.storage().instance()
+        .get(&GovDataKey::Config)
+        .expect("Not initialized")
+}
+
+/// Load a proposal or panic
+fn load_proposal(env: &Env, proposal_id: u64) -> Proposal {
+    env.storage().instance()
+        .get(&GovDataKey::Proposal(proposal_id))
+        .expect("Proposal not found")
+}
+
+/// Derive a proposal's state from its votes and the current ledger
+/// Quorum compares the raw stake of voters against total staked SST at the
+/// snapshot ledger, so lock bonuses weigh votes but not turnout
+fn proposal_state(env: &Env, config: &GovernanceConfig, proposal: &Proposal) -> ProposalState {
+    if proposal.executed {
+        return ProposalState::Executed;
+    }
+
+    let current = env.ledger().sequence();
+    if current <= proposal.vote_end_ledger {
+        return ProposalState::Active;
+    }
+
+    let total_staked = VotingTokenClient::new(env, &config.token)
+        .total_staked_at(&proposal.snapshot_ledger);
+    let quorum = total_staked * config.quorum_bps as i128 / 10_000;
+    if proposal.turnout < quorum || proposal.votes_for <= proposal.votes_against {
+        return ProposalState::Defeated;
+    }
+
+    let eta = proposal.vote_end_ledger + config.timelock;
+    if current <= eta {
+        ProposalState::Queued
+    } else if current <= eta + EXECUTION_WINDOW {
+        ProposalState::Executable
+    } else {
+        ProposalState::Expired
+    }
+}
+
+/// Test suite
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crowdfunding::{CrowdfundingContract, CrowdfundingContractClient};
+    use reward_token::{RewardTokenContract, RewardTokenContractClient};
+    use soroban_sdk::{testutils::{Address as _, Ledger}, String};
+
+    /// Register SST, crowdfunding and governance with governance as platform admin
+    fn setup(env: &Env) -> (
+        GovernanceContractClient<'_>,
+        CrowdfundingContractClient<'_>,
+        RewardTokenContractClient<'_>,
+    ) {
+        let token_id = env.register_contract(None, RewardTokenContract);
+        let token = RewardTokenContractClient::new(env, &token_id);
+        let crowdfunding_id = env.register_contract(None, CrowdfundingContract);
+        let crowdfunding = CrowdfundingContractClient::new(env, &crowdfunding_id);
+        let governance_id = env.register_contract(None, GovernanceContract);
+        let governance = GovernanceContractClient::new(env, &governance_id);
+
+        env.mock_all_auths();
+        token.initialize(
+            &crowdfunding_id,
+            &String::from_str(env, "Stellar Support Token"),
+            &String::from_str(env, "SST"),
+            &7,
+            &None,
+        );
+        token.add_minter(&crowdfunding_id, &1_000_000i128);
+        crowdfunding.set_admin(&governance_id);
+
+        // 20% quorum, 100 ledger vote, 50 ledger timelock, 100 SST to propose
+        governance.initialize(&token_id, &crowdfunding_id, &2_000, &100, &50, &100i128);
+
+        (governance, crowdfunding, token)
+    }
+
+    #[test]
+    fn test_proposal_lifecycle() {
+        let env = Env::default();
+        let (governance, crowdfunding, token) = setup(&env);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let treasury = Address::generate(&env);
+
+        env.ledger().set_sequence_number(10);
+        token.mint(&crowdfunding.address, &alice, &3_000i128);
+        token.mint(&crowdfunding.address, &bob, &1_000i128);
+        token.stake(&alice, &3_000i128, &1_000);
+        token.stake(&bob, &1_000i128, &1_000);
+
+        env.ledger().set_sequence_number(11);
+        let action = ProposalAction::SetPlatformFee(300, treasury.clone());
+        let proposal_id = governance.propose(&alice, &action);
+        assert_eq!(governance.state(&proposal_id), ProposalState::Active);
+
+        assert_eq!(governance.vote(&alice, &proposal_id, &true), 3_000i128);
+        assert_eq!(governance.vote(&bob, &proposal_id, &false), 1_000i128);
+
+        // Voting ends at ledger 111, timelock until 161
+        env.ledger().set_sequence_number(112);
+        assert_eq!(governance.state(&proposal_id), ProposalState::Queued);
+        assert!(governance.try_execute(&proposal_id).is_err());
+
+        env.ledger().set_sequence_number(162);
+        assert_eq!(governance.state(&proposal_id), ProposalState::Executable);
+        governance.execute(&proposal_id);
+
+        assert_eq!(governance.state(&proposal_id), ProposalState::Executed);
+        let fee = crowdfunding.get_fee_config().unwrap();
+        assert_eq!(fee.fee_bps, 300);
+        assert_eq!(fee.treasury, treasury);
+    }
+
+    #[test]
+    fn test_admin_actions_reach_crowdfunding() {
+        let env = Env::default();
+        let (governance, crowdfunding, token) = setup(&env);
+
+        let alice = Address::generate(&env);
+        let asset = Address::generate(&env);
+        let successor = Address::generate(&env);
+
+        env.ledger().set_sequence_number(10);
+        token.mint(&crowdfunding.address, &alice, &1_000i128);
+        token.stake(&alice, &1_000i128, &10_000);
+
+        env.ledger().set_sequence_number(11);
+        let bond = ProposalAction::SetCreationBond(BondConfig { asset: asset.clone(), amount: 50 });
+        let bond_id = governance.propose(&alice, &bond);
+        let handover_id = governance.propose(&alice, &ProposalAction::TransferAdmin(successor.clone()));
+        governance.vote(&alice, &bond_id, &true);
+        governance.vote(&alice, &handover_id, &true);
+
+        env.ledger().set_sequence_number(162);
+        governance.execute(&bond_id);
+        let config = crowdfunding.get_creation_bond().unwrap();
+        assert_eq!((config.asset, config.amount), (asset, 50i128));
+
+        // Governance can hand the platform over, e.g. to an upgraded contract
+        governance.execute(&handover_id);
+        assert_eq!(crowdfunding.get_admin(), Some(successor));
+    }
+
+    #[test]
+    #[should_panic(expected = "Proposal cannot be executed")]
+    fn test_proposal_defeated_without_quorum() {
+        let env = Env::default();
+        let (governance, crowdfunding, token) = setup(&env);
+
+        let alice = Address::generate(&env);
+        let whale = Address::generate(&env);
+
+        env.ledger().set_sequence_number(10);
+        token.mint(&crowdfunding.address, &alice, &1_000i128);
+        token.mint(&crowdfunding.address, &whale, &9_000i128);
+        token.stake(&alice, &1_000i128, &6_307_200);
+        token.stake(&whale, &9_000i128, &1_000);
+
+        // Alice alone is 10% of staked SST, below the 20% quorum even though
+        // her maximum lock doubles her voting power
+        env.ledger().set_sequence_number(11);
+        let proposal_id = governance.propose(&alice, &ProposalAction::SetPaused(true));
+        assert_eq!(governance.vote(&alice, &proposal_id, &true), 2_000i128);
+
+        env.ledger().set_sequence_number(200);
+        assert_eq!(governance.state(&proposal_id), ProposalState::Defeated);
+        governance.execute(&proposal_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient voting power to propose")]
+    fn test_propose_below_threshold() {
+        let env = Env::default();
+        let (governance, crowdfunding, token) = setup(&env);
+
+        let alice = Address::generate(&env);
+
+        env.ledger().set_sequence_number(10);
+        token.mint(&crowdfunding.address, &alice, &50i128);
+        token.stake(&alice, &50i128, &1_000);
+
+        env.ledger().set_sequence_number(11);
+        governance.propose(&alice, &ProposalAction::SetRewardRate(5));
+    }
+}
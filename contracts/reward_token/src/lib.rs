@@ -4,18 +4,81 @@
 //! Designed for inter-contract calls from the CrowdfundingContract.
 //!
 //! Features:
-//! - Admin-only minting (only the crowdfunding contract can mint)
+//! - Minting restricted to admin-registered minters with allowances
 //! - Balance tracking per wallet
 //! - Transfer between wallets
+//! - Vesting mints with a cliff and linear release
+//! - Holder-authorized burning
+//! - Minter clawback of its own issuance (vesting, liquid, then staked) with debt recorded for shortfalls
+//! - Staking with time-weighted, checkpointed voting power
+//! - Historical balance and total supply checkpoints
+//! - Optional supply cap and per-window mint rate limits
 //! - Total supply tracking
 //! - Structured event emission
 
 #![no_std]
 use soroban_sdk::{
     contract, contractimpl, contracttype, symbol_short,
-    Address, Env, log,
+    Address, Env, IntoVal, TryFromVal, Val, Vec, log,
 };
 
+/// Longest lock accepted by `stake`, in ledgers (~1 year at 5s per ledger)
+const MAX_LOCK_LEDGERS: u32 = 6_307_200;
+
+/// Locked SST position
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct StakePosition {
+    pub amount: i128,
+    pub unlock_ledger: u32,
+}
+
+/// Stake state recorded from `ledger` onwards
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct StakeCheckpoint {
+    pub ledger: u32,
+    pub amount: i128,
+    pub unlock_ledger: u32,
+}
+
+/// Amount recorded from `ledger` onwards
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub amount: i128,
+}
+
+/// Admin-tunable mint rate limits over a rolling ledger window
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct MintLimits {
+    pub window_ledgers: u32,
+    pub period_budget: i128,   // Max minted per window across all recipients, 0 = unlimited
+    pub recipient_limit: i128, // Max minted per window to one recipient, 0 = unlimited
+}
+
+/// Amount minted within a window
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct MintWindow {
+    pub window: u32,
+    pub minted: i128,
+}
+
+/// SST released linearly between `cliff_ledger` and `end_ledger`
+/// Nothing is claimable before the cliff; `claimed` tracks what has been released
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub struct VestingSchedule {
+    pub amount: i128,
+    pub claimed: i128,
+    pub start_ledger: u32,
+    pub cliff_ledger: u32,
+    pub end_ledger: u32,
+}
+
 /// Storage keys for the token contract
 #[contracttype]
 pub enum TokenDataKey {
@@ -25,6 +88,20 @@ pub enum TokenDataKey {
     TokenName,
     TokenSymbol,
     Decimals,
+    Debt(Address),
+    Stake(Address),
+    StakeCheckpoints(Address),
+    TotalStakedCheckpoints,
+    BalanceCheckpoints(Address),
+    SupplyCheckpoints,
+    SupplyCap,
+    MintLimits,
+    MintWindow,
+    RecipientWindow(Address),
+    Minter(Address), // Remaining mint allowance of a registered minter
+    MinterList,
+    Vesting(Address),
+    Issued(Address, Address), // Amount a minter has issued to a holder and may claw back
 }
 
 #[contract]
@@ -34,17 +111,32 @@ pub struct RewardTokenContract;
 impl RewardTokenContract {
     /// Initialize the token contract
     /// Called once after deployment to set the admin (crowdfunding contract)
-    pub fn initialize(env: Env, admin: Address, name: soroban_sdk::String, symbol: soroban_sdk::String, decimals: u32) {
+    /// and an optional hard cap on total supply
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        name: soroban_sdk::String,
+        symbol: soroban_sdk::String,
+        decimals: u32,
+        supply_cap: Option<i128>,
+    ) {
         // Prevent re-initialization
         if env.storage().instance().has(&TokenDataKey::Admin) {
             panic!("Already initialized");
         }
 
+        if let Some(cap) = supply_cap {
+            if cap <= 0 {
+                panic!("Supply cap must be positive");
+            }
+            env.storage().instance().set(&TokenDataKey::SupplyCap, &cap);
+        }
+
         env.storage().instance().set(&TokenDataKey::Admin, &admin);
         env.storage().instance().set(&TokenDataKey::TokenName, &name);
         env.storage().instance().set(&TokenDataKey::TokenSymbol, &symbol);
         env.storage().instance().set(&TokenDataKey::Decimals, &decimals);
-        env.storage().instance().set(&TokenDataKey::TotalSupply, &0i128);
+        set_total_supply(&env, 0);
 
         env.storage().instance().extend_ttl(100_000, 100_000);
 
@@ -56,45 +148,255 @@ impl RewardTokenContract {
         log!(&env, "RewardToken initialized");
     }
 
-    /// Mint tokens to a recipient (admin-only)
-    /// This is called by the CrowdfundingContract via inter-contract call
-    /// Note: Authorization is handled by the calling contract
-    pub fn mint(env: Env, to: Address, amount: i128) {
+    /// Register a minter with a mint allowance (admin-only)
+    pub fn add_minter(env: Env, minter: Address, allowance: i128) {
+        require_admin(&env);
+
+        if env.storage().instance().has(&TokenDataKey::Minter(minter.clone())) {
+            panic!("Already a minter");
+        }
+
+        if allowance < 0 {
+            panic!("Allowance cannot be negative");
+        }
+
+        env.storage().instance().set(&TokenDataKey::Minter(minter.clone()), &allowance);
+
+        let mut minters = Self::minters(env.clone());
+        minters.push_back(minter.clone());
+        env.storage().instance().set(&TokenDataKey::MinterList, &minters);
+
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("MINTER"), symbol_short!("added")),
+            (minter, allowance),
+        );
+    }
+
+    /// Remove a minter (admin-only)
+    pub fn remove_minter(env: Env, minter: Address) {
+        require_admin(&env);
+
+        if !env.storage().instance().has(&TokenDataKey::Minter(minter.clone())) {
+            panic!("Not a minter");
+        }
+
+        env.storage().instance().remove(&TokenDataKey::Minter(minter.clone()));
+
+        let mut minters = Self::minters(env.clone());
+        if let Some(index) = minters.first_index_of(&minter) {
+            minters.remove(index);
+        }
+        env.storage().instance().set(&TokenDataKey::MinterList, &minters);
+
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("MINTER"), symbol_short!("removed")),
+            minter,
+        );
+    }
+
+    /// Set a minter's remaining allowance (admin-only)
+    pub fn set_minter_allowance(env: Env, minter: Address, allowance: i128) {
+        require_admin(&env);
+
+        if !env.storage().instance().has(&TokenDataKey::Minter(minter.clone())) {
+            panic!("Not a minter");
+        }
+
+        if allowance < 0 {
+            panic!("Allowance cannot be negative");
+        }
+
+        env.storage().instance().set(&TokenDataKey::Minter(minter.clone()), &allowance);
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("MINTER"), symbol_short!("allowance")),
+            (minter, allowance),
+        );
+    }
+
+    /// Get registered minters
+    pub fn minters(env: Env) -> Vec<Address> {
+        env.storage().instance()
+            .get(&TokenDataKey::MinterList)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get a minter's remaining allowance, or None if not registered
+    pub fn minter_allowance(env: Env, minter: Address) -> Option<i128> {
+        env.storage().instance().get(&TokenDataKey::Minter(minter))
+    }
+
+    /// Mint tokens to a recipient (registered minters only)
+    /// Called by CrowdfundingContract instances via inter-contract call.
+    /// Requests beyond the supply cap are rejected.
+    /// Returns the amount credited after debt settlement
+    pub fn mint(env: Env, minter: Address, to: Address, amount: i128) -> i128 {
+        minter.require_auth();
+        mint_liquid(&env, &minter, &to, amount, false)
+    }
+
+    /// Mint up to `amount`, issuing only the room left under the supply cap
+    /// (registered minters only). Fails once the cap is reached.
+    /// Returns the amount credited after debt settlement and the cap
+    pub fn mint_up_to(env: Env, minter: Address, to: Address, amount: i128) -> i128 {
+        minter.require_auth();
+        mint_liquid(&env, &minter, &to, amount, true)
+    }
+
+    /// Mint tokens into a vesting schedule instead of a liquid balance (registered minters only)
+    /// Nothing unlocks before `cliff_ledgers` have passed; the full amount is
+    /// released linearly until `duration_ledgers` after the mint.
+    /// Requests beyond the supply cap are rejected, as with `mint`.
+    /// Returns the amount placed in vesting after debt settlement
+    pub fn mint_vested(
+        env: Env,
+        minter: Address,
+        to: Address,
+        amount: i128,
+        cliff_ledgers: u32,
+        duration_ledgers: u32,
+    ) -> i128 {
+        minter.require_auth();
+
         if amount <= 0 {
             panic!("Mint amount must be positive");
         }
 
-        // Update recipient balance
-        let current_balance: i128 = env.storage().instance()
-            .get(&TokenDataKey::Balance(to.clone()))
-            .unwrap_or(0);
+        if duration_ledgers == 0 || cliff_ledgers > duration_ledgers {
+            panic!("Invalid vesting schedule");
+        }
 
-        let new_balance = current_balance
-            .checked_add(amount)
-            .expect("Balance overflow");
+        let (amount, new_supply) = issue(&env, &minter, &to, amount, false);
+        if amount == 0 {
+            env.storage().instance().extend_ttl(100_000, 100_000);
+            return 0;
+        }
 
-        env.storage().instance().set(&TokenDataKey::Balance(to.clone()), &new_balance);
+        let now = env.ledger().sequence();
+        let schedule = VestingSchedule {
+            amount,
+            claimed: 0,
+            start_ledger: now,
+            cliff_ledger: now + cliff_ledgers,
+            end_ledger: now + duration_ledgers,
+        };
 
-        // Update total supply
-        let total_supply: i128 = env.storage().instance()
-            .get(&TokenDataKey::TotalSupply)
+        let mut schedules = Self::vesting_schedules(env.clone(), to.clone());
+        schedules.push_back(schedule.clone());
+        env.storage().instance().set(&TokenDataKey::Vesting(to.clone()), &schedules);
+
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("TOKEN"), symbol_short!("vested")),
+            (to, amount, schedule.cliff_ledger, schedule.end_ledger, new_supply),
+        );
+
+        log!(&env, "Minted {} SST tokens into vesting", amount);
+
+        amount
+    }
+
+    /// Move all unlocked vesting tokens into the account's balance
+    /// Returns the amount released
+    pub fn claim_vested(env: Env, account: Address) -> i128 {
+        account.require_auth();
+
+        let now = env.ledger().sequence();
+        let schedules = Self::vesting_schedules(env.clone(), account.clone());
+
+        let mut released = 0i128;
+        let mut remaining = Vec::new(&env);
+        for mut schedule in schedules.iter() {
+            let claimable = vested_amount(&schedule, now) - schedule.claimed;
+            released += claimable;
+            schedule.claimed += claimable;
+            if schedule.claimed < schedule.amount {
+                remaining.push_back(schedule);
+            }
+        }
+
+        if released == 0 {
+            panic!("Nothing to claim");
+        }
+
+        env.storage().instance().set(&TokenDataKey::Vesting(account.clone()), &remaining);
+
+        let balance: i128 = env.storage().instance()
+            .get(&TokenDataKey::Balance(account.clone()))
             .unwrap_or(0);
+        set_balance(&env, &account, balance + released);
 
-        let new_supply = total_supply
-            .checked_add(amount)
-            .expect("Supply overflow");
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("TOKEN"), symbol_short!("claimed")),
+            (account, released),
+        );
+
+        released
+    }
+
+    /// Get an account's outstanding vesting schedules
+    pub fn vesting_schedules(env: Env, account: Address) -> Vec<VestingSchedule> {
+        env.storage().instance()
+            .get(&TokenDataKey::Vesting(account))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get vesting tokens that have not unlocked yet
+    pub fn locked_balance(env: Env, account: Address) -> i128 {
+        let now = env.ledger().sequence();
+        Self::vesting_schedules(env, account)
+            .iter()
+            .map(|schedule| schedule.amount - vested_amount(&schedule, now))
+            .sum()
+    }
+
+    /// Get vesting tokens that have unlocked but not been claimed
+    pub fn unlocked_balance(env: Env, account: Address) -> i128 {
+        let now = env.ledger().sequence();
+        Self::vesting_schedules(env, account)
+            .iter()
+            .map(|schedule| vested_amount(&schedule, now) - schedule.claimed)
+            .sum()
+    }
+
+    /// Configure mint rate limits (admin-only)
+    pub fn set_mint_limits(env: Env, window_ledgers: u32, period_budget: i128, recipient_limit: i128) {
+        require_admin(&env);
+
+        if window_ledgers == 0 {
+            panic!("Window must be positive");
+        }
 
-        env.storage().instance().set(&TokenDataKey::TotalSupply, &new_supply);
+        if period_budget < 0 || recipient_limit < 0 {
+            panic!("Limits cannot be negative");
+        }
 
+        let limits = MintLimits { window_ledgers, period_budget, recipient_limit };
+        env.storage().instance().set(&TokenDataKey::MintLimits, &limits);
         env.storage().instance().extend_ttl(100_000, 100_000);
 
-        // Emit TokensMinted event
         env.events().publish(
-            (symbol_short!("TOKEN"), symbol_short!("minted")),
-            (to, amount, new_supply),
+            (symbol_short!("TOKEN"), symbol_short!("limits")),
+            (window_ledgers, period_budget, recipient_limit),
         );
+    }
+
+    /// Get mint rate limits
+    pub fn mint_limits(env: Env) -> Option<MintLimits> {
+        env.storage().instance().get(&TokenDataKey::MintLimits)
+    }
 
-        log!(&env, "Minted {} SST tokens", amount);
+    /// Get the hard cap on total supply
+    pub fn supply_cap(env: Env) -> Option<i128> {
+        env.storage().instance().get(&TokenDataKey::SupplyCap)
     }
 
     /// Transfer tokens between wallets
@@ -120,7 +422,7 @@ impl RewardTokenContract {
 
         // Update balances
         let new_from_balance = from_balance - amount;
-        env.storage().instance().set(&TokenDataKey::Balance(from.clone()), &new_from_balance);
+        set_balance(&env, &from, new_from_balance);
 
         let to_balance: i128 = env.storage().instance()
             .get(&TokenDataKey::Balance(to.clone()))
@@ -130,7 +432,7 @@ impl RewardTokenContract {
             .checked_add(amount)
             .expect("Balance overflow");
 
-        env.storage().instance().set(&TokenDataKey::Balance(to.clone()), &new_to_balance);
+        set_balance(&env, &to, new_to_balance);
 
         env.storage().instance().extend_ttl(100_000, 100_000);
 
@@ -140,6 +442,287 @@ impl RewardTokenContract {
         );
     }
 
+    /// Burn tokens from a wallet (holder-authorized)
+    pub fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+
+        if amount <= 0 {
+            panic!("Burn amount must be positive");
+        }
+
+        let balance: i128 = env.storage().instance()
+            .get(&TokenDataKey::Balance(from.clone()))
+            .unwrap_or(0);
+
+        if balance < amount {
+            panic!("Insufficient balance");
+        }
+
+        set_balance(&env, &from, balance - amount);
+
+        let total_supply: i128 = env.storage().instance()
+            .get(&TokenDataKey::TotalSupply)
+            .unwrap_or(0);
+        let new_supply = total_supply - amount;
+        set_total_supply(&env, new_supply);
+
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("TOKEN"), symbol_short!("burned")),
+            (from, amount, new_supply),
+        );
+    }
+
+    /// Reclaim tokens from a wallet (registered minters only)
+    /// Called by a CrowdfundingContract when a donation is refunded.
+    /// A minter can only reclaim what it issued to `from`; larger requests are capped.
+    /// Unclaimed vesting tokens are taken first (newest first), then liquid balance,
+    /// then staked SST.
+    /// Any remaining shortfall is recorded as debt and settled from future mints.
+    /// Returns the amount actually reclaimed.
+    pub fn clawback(env: Env, minter: Address, from: Address, amount: i128) -> i128 {
+        minter.require_auth();
+
+        if amount <= 0 {
+            panic!("Clawback amount must be positive");
+        }
+
+        if !env.storage().instance().has(&TokenDataKey::Minter(minter.clone())) {
+            panic!("Not a minter");
+        }
+
+        let issued_key = TokenDataKey::Issued(minter, from.clone());
+        let issued: i128 = env.storage().instance().get(&issued_key).unwrap_or(0);
+        let amount = amount.min(issued);
+        env.storage().instance().set(&issued_key, &(issued - amount));
+
+        let balance: i128 = env.storage().instance()
+            .get(&TokenDataKey::Balance(from.clone()))
+            .unwrap_or(0);
+
+        let mut from_vesting = 0i128;
+        let mut schedules = Self::vesting_schedules(env.clone(), from.clone());
+        let mut index = schedules.len();
+        while index > 0 && from_vesting < amount {
+            index -= 1;
+            let mut schedule = schedules.get(index).unwrap();
+            let taken = (schedule.amount - schedule.claimed).min(amount - from_vesting);
+            from_vesting += taken;
+            schedule.amount -= taken;
+            if schedule.amount == schedule.claimed {
+                schedules.remove(index);
+            } else {
+                schedules.set(index, schedule);
+            }
+        }
+
+        if from_vesting > 0 {
+            env.storage().instance().set(&TokenDataKey::Vesting(from.clone()), &schedules);
+        }
+
+        let from_balance = balance.min(amount - from_vesting);
+        if from_balance > 0 {
+            set_balance(&env, &from, balance - from_balance);
+        }
+
+        // Staked SST is reclaimed last, whether or not its lock has ended
+        let mut from_stake = 0i128;
+        let stake: Option<StakePosition> = env.storage().instance()
+            .get(&TokenDataKey::Stake(from.clone()));
+        if let Some(mut position) = stake {
+            from_stake = position.amount.min(amount - from_vesting - from_balance);
+            if from_stake > 0 {
+                position.amount -= from_stake;
+                if position.amount == 0 {
+                    env.storage().instance().remove(&TokenDataKey::Stake(from.clone()));
+                    position.unlock_ledger = 0;
+                } else {
+                    env.storage().instance().set(&TokenDataKey::Stake(from.clone()), &position);
+                }
+                write_stake_checkpoint(&env, &from, &position);
+                write_total_staked(&env, -from_stake);
+            }
+        }
+
+        let clawed = from_vesting + from_balance + from_stake;
+        let shortfall = amount - clawed;
+
+        if clawed > 0 {
+
+            let total_supply: i128 = env.storage().instance()
+                .get(&TokenDataKey::TotalSupply)
+                .unwrap_or(0);
+            set_total_supply(&env, total_supply - clawed);
+        }
+
+        if shortfall > 0 {
+            let debt: i128 = env.storage().instance()
+                .get(&TokenDataKey::Debt(from.clone()))
+                .unwrap_or(0);
+            let new_debt = debt.checked_add(shortfall).expect("Debt overflow");
+            env.storage().instance().set(&TokenDataKey::Debt(from.clone()), &new_debt);
+        }
+
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("TOKEN"), symbol_short!("clawback")),
+            (from, clawed, shortfall),
+        );
+
+        clawed
+    }
+
+    /// Get outstanding clawback debt for an address
+    pub fn debt(env: Env, account: Address) -> i128 {
+        env.storage().instance()
+            .get(&TokenDataKey::Debt(account))
+            .unwrap_or(0)
+    }
+
+    /// Lock SST for `lock_ledgers` ledgers to gain voting power
+    /// Adding to an existing position keeps the later of the two unlock ledgers
+    pub fn stake(env: Env, account: Address, amount: i128, lock_ledgers: u32) {
+        account.require_auth();
+
+        if amount <= 0 {
+            panic!("Stake amount must be positive");
+        }
+
+        if lock_ledgers == 0 || lock_ledgers > MAX_LOCK_LEDGERS {
+            panic!("Invalid lock period");
+        }
+
+        let balance: i128 = env.storage().instance()
+            .get(&TokenDataKey::Balance(account.clone()))
+            .unwrap_or(0);
+
+        if balance < amount {
+            panic!("Insufficient balance");
+        }
+
+        set_balance(&env, &account, balance - amount);
+
+        let current = env.ledger().sequence();
+        let mut position: StakePosition = env.storage().instance()
+            .get(&TokenDataKey::Stake(account.clone()))
+            .unwrap_or(StakePosition { amount: 0, unlock_ledger: 0 });
+
+        position.amount = position.amount.checked_add(amount).expect("Stake overflow");
+        position.unlock_ledger = position.unlock_ledger.max(current + lock_ledgers);
+        env.storage().instance().set(&TokenDataKey::Stake(account.clone()), &position);
+
+        write_stake_checkpoint(&env, &account, &position);
+        write_total_staked(&env, amount);
+
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("STAKE"), symbol_short!("locked")),
+            (account, amount, position.unlock_ledger),
+        );
+    }
+
+    /// Release the whole staked position once its lock has ended
+    pub fn unstake(env: Env, account: Address) -> i128 {
+        account.require_auth();
+
+        let position: StakePosition = env.storage().instance()
+            .get(&TokenDataKey::Stake(account.clone()))
+            .expect("No stake");
+
+        if env.ledger().sequence() < position.unlock_ledger {
+            panic!("Stake is still locked");
+        }
+
+        env.storage().instance().remove(&TokenDataKey::Stake(account.clone()));
+
+        let balance: i128 = env.storage().instance()
+            .get(&TokenDataKey::Balance(account.clone()))
+            .unwrap_or(0);
+        let new_balance = balance.checked_add(position.amount).expect("Balance overflow");
+        set_balance(&env, &account, new_balance);
+
+        write_stake_checkpoint(&env, &account, &StakePosition { amount: 0, unlock_ledger: 0 });
+        write_total_staked(&env, -position.amount);
+
+        env.storage().instance().extend_ttl(100_000, 100_000);
+
+        env.events().publish(
+            (symbol_short!("STAKE"), symbol_short!("unlocked")),
+            (account, position.amount),
+        );
+
+        position.amount
+    }
+
+    /// Get the staked position of an address
+    pub fn get_stake(env: Env, account: Address) -> Option<StakePosition> {
+        env.storage().instance().get(&TokenDataKey::Stake(account))
+    }
+
+    /// Voting power of an address at a ledger
+    /// Staked SST counts 1x plus a bonus of up to 1x that decays linearly
+    /// as the remaining lock shrinks from `MAX_LOCK_LEDGERS` to zero
+    pub fn voting_power(env: Env, account: Address, at_ledger: u32) -> i128 {
+        let checkpoints: Vec<StakeCheckpoint> = env.storage().instance()
+            .get(&TokenDataKey::StakeCheckpoints(account))
+            .unwrap_or(Vec::new(&env));
+
+        match checkpoint_at(&checkpoints, at_ledger) {
+            Some(checkpoint) => {
+                let remaining = checkpoint.unlock_ledger.saturating_sub(at_ledger).min(MAX_LOCK_LEDGERS);
+                checkpoint.amount + checkpoint.amount * remaining as i128 / MAX_LOCK_LEDGERS as i128
+            }
+            None => 0,
+        }
+    }
+
+    /// SST staked by an address at a ledger, without the lock bonus
+    pub fn staked_at(env: Env, account: Address, at_ledger: u32) -> i128 {
+        let checkpoints: Vec<StakeCheckpoint> = env.storage().instance()
+            .get(&TokenDataKey::StakeCheckpoints(account))
+            .unwrap_or(Vec::new(&env));
+
+        checkpoint_at(&checkpoints, at_ledger)
+            .map(|checkpoint| checkpoint.amount)
+            .unwrap_or(0)
+    }
+
+    /// Total SST staked at a ledger
+    pub fn total_staked_at(env: Env, at_ledger: u32) -> i128 {
+        let checkpoints: Vec<Checkpoint> = env.storage().instance()
+            .get(&TokenDataKey::TotalStakedCheckpoints)
+            .unwrap_or(Vec::new(&env));
+
+        checkpoint_at(&checkpoints, at_ledger)
+            .map(|checkpoint| checkpoint.amount)
+            .unwrap_or(0)
+    }
+
+    /// Liquid balance of an address at a ledger (staked SST excluded, as in `balance`)
+    pub fn balance_at(env: Env, account: Address, at_ledger: u32) -> i128 {
+        let checkpoints: Vec<Checkpoint> = env.storage().instance()
+            .get(&TokenDataKey::BalanceCheckpoints(account))
+            .unwrap_or(Vec::new(&env));
+
+        checkpoint_at(&checkpoints, at_ledger)
+            .map(|checkpoint| checkpoint.amount)
+            .unwrap_or(0)
+    }
+
+    /// Total supply at a ledger
+    pub fn total_supply_at(env: Env, at_ledger: u32) -> i128 {
+        let checkpoints: Vec<Checkpoint> = env.storage().instance()
+            .get(&TokenDataKey::SupplyCheckpoints)
+            .unwrap_or(Vec::new(&env));
+
+        checkpoint_at(&checkpoints, at_ledger)
+            .map(|checkpoint| checkpoint.amount)
+            .unwrap_or(0)
+    }
+
     /// Get balance for an address
     pub fn balance(env: Env, account: Address) -> i128 {
         env.storage().instance()
@@ -183,11 +766,274 @@ impl RewardTokenContract {
     }
 }
 
+/// Require auth from the token admin
+fn require_admin(env: &Env) -> Address {
+    let admin: Address = env.storage().instance()
+        .get(&TokenDataKey::Admin)
+        .expect("Not initialized");
+    admin.require_auth();
+    admin
+}
+
+/// Charge a mint against the minter's allowance, rate limits and supply cap
+/// Outstanding clawback debt is settled first; the rest must fit under the cap
+/// unless `clamp` limits it to the room left. Returns the amount to credit to
+/// the recipient (0 if fully absorbed by debt) and the new supply
+fn issue(env: &Env, minter: &Address, to: &Address, amount: i128, clamp: bool) -> (i128, i128) {
+    let allowance: i128 = env.storage().instance()
+        .get(&TokenDataKey::Minter(minter.clone()))
+        .expect("Not a minter");
+
+    let debt: i128 = env.storage().instance()
+        .get(&TokenDataKey::Debt(to.clone()))
+        .unwrap_or(0);
+
+    let settled = debt.min(amount);
+    if settled > 0 {
+        env.storage().instance().set(&TokenDataKey::Debt(to.clone()), &(debt - settled));
+
+        env.events().publish(
+            (symbol_short!("TOKEN"), symbol_short!("debtpaid")),
+            (to.clone(), settled, debt - settled),
+        );
+    }
+
+    let total_supply: i128 = env.storage().instance()
+        .get(&TokenDataKey::TotalSupply)
+        .unwrap_or(0);
+
+    let mut amount = amount - settled;
+    if amount == 0 {
+        return (0, total_supply);
+    }
+
+    let supply_cap: Option<i128> = env.storage().instance().get(&TokenDataKey::SupplyCap);
+    if let Some(cap) = supply_cap {
+        let room = cap - total_supply;
+        if amount > room {
+            if !clamp || room == 0 {
+                panic!("Supply cap exceeded");
+            }
+            amount = room;
+        }
+    }
+
+    if amount > allowance {
+        panic!("Minter allowance exceeded");
+    }
+    env.storage().instance().set(&TokenDataKey::Minter(minter.clone()), &(allowance - amount));
+
+    let issued_key = TokenDataKey::Issued(minter.clone(), to.clone());
+    let issued: i128 = env.storage().instance().get(&issued_key).unwrap_or(0);
+    env.storage().instance().set(&issued_key, &(issued + amount));
+
+    enforce_mint_limits(env, to, amount);
+
+    let new_supply = total_supply
+        .checked_add(amount)
+        .expect("Supply overflow");
+
+    if supply_cap == Some(new_supply) {
+        env.events().publish(
+            (symbol_short!("TOKEN"), symbol_short!("capped")),
+            new_supply,
+        );
+    }
+
+    set_total_supply(env, new_supply);
+
+    (amount, new_supply)
+}
+
+/// Credit a liquid mint; `clamp` issues only the room left under the supply cap
+fn mint_liquid(env: &Env, minter: &Address, to: &Address, amount: i128, clamp: bool) -> i128 {
+    if amount <= 0 {
+        panic!("Mint amount must be positive");
+    }
+
+    let (amount, new_supply) = issue(env, minter, to, amount, clamp);
+    if amount == 0 {
+        env.storage().instance().extend_ttl(100_000, 100_000);
+        return 0;
+    }
+
+    // Update recipient balance
+    let current_balance: i128 = env.storage().instance()
+        .get(&TokenDataKey::Balance(to.clone()))
+        .unwrap_or(0);
+
+    let new_balance = current_balance
+        .checked_add(amount)
+        .expect("Balance overflow");
+
+    set_balance(env, to, new_balance);
+
+    env.storage().instance().extend_ttl(100_000, 100_000);
+
+    // Emit TokensMinted event
+    env.events().publish(
+        (symbol_short!("TOKEN"), symbol_short!("minted")),
+        (to.clone(), amount, new_supply),
+    );
+
+    log!(env, "Minted {} SST tokens", amount);
+
+    amount
+}
+
+/// Portion of a vesting schedule unlocked at `ledger`
+/// Never less than what was already claimed, since clawbacks can shrink `amount`
+fn vested_amount(schedule: &VestingSchedule, ledger: u32) -> i128 {
+    let vested = if ledger < schedule.cliff_ledger {
+        0
+    } else if ledger >= schedule.end_ledger {
+        schedule.amount
+    } else {
+        let elapsed = (ledger - schedule.start_ledger) as i128;
+        let duration = (schedule.end_ledger - schedule.start_ledger) as i128;
+        schedule.amount * elapsed / duration
+    };
+    vested.max(schedule.claimed)
+}
+
+/// Count a mint against the window budget and recipient limit, rejecting excess
+fn enforce_mint_limits(env: &Env, to: &Address, amount: i128) {
+    let limits: MintLimits = match env.storage().instance().get(&TokenDataKey::MintLimits) {
+        Some(limits) => limits,
+        None => return,
+    };
+
+    let window = env.ledger().sequence() / limits.window_ledgers;
+    let window_minted = |key: &TokenDataKey| -> i128 {
+        env.storage().instance()
+            .get::<_, MintWindow>(key)
+            .filter(|record| record.window == window)
+            .map(|record| record.minted)
+            .unwrap_or(0)
+    };
+
+    let period_minted = window_minted(&TokenDataKey::MintWindow) + amount;
+    if limits.period_budget > 0 && period_minted > limits.period_budget {
+        panic!("Mint budget exceeded");
+    }
+
+    let recipient_key = TokenDataKey::RecipientWindow(to.clone());
+    let recipient_minted = window_minted(&recipient_key) + amount;
+    if limits.recipient_limit > 0 && recipient_minted > limits.recipient_limit {
+        panic!("Recipient mint limit exceeded");
+    }
+
+    env.storage().instance().set(
+        &TokenDataKey::MintWindow,
+        &MintWindow { window, minted: period_minted },
+    );
+    env.storage().instance().set(
+        &recipient_key,
+        &MintWindow { window, minted: recipient_minted },
+    );
+}
+
+/// Records stored in ledger order
+trait Ledgered: Clone + IntoVal<Env, Val> + TryFromVal<Env, Val> {
+    fn ledger(&self) -> u32;
+}
+
+impl Ledgered for Checkpoint {
+    fn ledger(&self) -> u32 {
+        self.ledger
+    }
+}
+
+impl Ledgered for StakeCheckpoint {
+    fn ledger(&self) -> u32 {
+        self.ledger
+    }
+}
+
+/// Append a checkpoint, replacing one already written in the same ledger
+fn push_checkpoint<T: Ledgered>(checkpoints: &mut Vec<T>, checkpoint: T) {
+    if let Some(last) = checkpoints.last() {
+        if last.ledger() == checkpoint.ledger() {
+            checkpoints.set(checkpoints.len() - 1, checkpoint);
+            return;
+        }
+    }
+    checkpoints.push_back(checkpoint);
+}
+
+/// Latest checkpoint at or before `ledger` (binary search)
+fn checkpoint_at<T: Ledgered>(checkpoints: &Vec<T>, ledger: u32) -> Option<T> {
+    let mut low = 0u32;
+    let mut high = checkpoints.len();
+    while low < high {
+        let mid = (low + high) / 2;
+        if checkpoints.get(mid).unwrap().ledger() <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    if low == 0 {
+        None
+    } else {
+        checkpoints.get(low - 1)
+    }
+}
+
+/// Record an account's stake from the current ledger onwards
+fn write_stake_checkpoint(env: &Env, account: &Address, position: &StakePosition) {
+    let key = TokenDataKey::StakeCheckpoints(account.clone());
+    let mut checkpoints: Vec<StakeCheckpoint> = env.storage().instance()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    push_checkpoint(&mut checkpoints, StakeCheckpoint {
+        ledger: env.ledger().sequence(),
+        amount: position.amount,
+        unlock_ledger: position.unlock_ledger,
+    });
+    env.storage().instance().set(&key, &checkpoints);
+}
+
+/// Record the total staked amount after applying `delta`
+fn write_total_staked(env: &Env, delta: i128) {
+    let checkpoints: Vec<Checkpoint> = env.storage().instance()
+        .get(&TokenDataKey::TotalStakedCheckpoints)
+        .unwrap_or(Vec::new(env));
+    let total = checkpoints.last().map(|checkpoint| checkpoint.amount).unwrap_or(0) + delta;
+    write_checkpoint(env, &TokenDataKey::TotalStakedCheckpoints, total);
+}
+
+/// Record an amount checkpoint at the current ledger
+fn write_checkpoint(env: &Env, key: &TokenDataKey, amount: i128) {
+    let mut checkpoints: Vec<Checkpoint> = env.storage().instance()
+        .get(key)
+        .unwrap_or(Vec::new(env));
+    push_checkpoint(&mut checkpoints, Checkpoint {
+        ledger: env.ledger().sequence(),
+        amount,
+    });
+    env.storage().instance().set(key, &checkpoints);
+}
+
+/// Store a balance and checkpoint it
+fn set_balance(env: &Env, account: &Address, balance: i128) {
+    env.storage().instance().set(&TokenDataKey::Balance(account.clone()), &balance);
+    write_checkpoint(env, &TokenDataKey::BalanceCheckpoints(account.clone()), balance);
+}
+
+/// Store the total supply and checkpoint it
+fn set_total_supply(env: &Env, supply: i128) {
+    env.storage().instance().set(&TokenDataKey::TotalSupply, &supply);
+    write_checkpoint(env, &TokenDataKey::SupplyCheckpoints, supply);
+}
+
 /// Test suite
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, String};
+    use soroban_sdk::{testutils::{Address as _, Ledger}, String};
+
+    const MINT_ALLOWANCE: i128 = 1_000_000_000_000;
 
     #[test]
     fn test_initialize() {
@@ -200,7 +1046,8 @@ mod test {
         let symbol = String::from_str(&env, "SST");
 
         env.mock_all_auths();
-        client.initialize(&admin, &name, &symbol, &7);
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
 
         assert_eq!(client.name(), name);
         assert_eq!(client.symbol(), symbol);
@@ -220,8 +1067,9 @@ mod test {
         let symbol = String::from_str(&env, "SST");
 
         env.mock_all_auths();
-        client.initialize(&admin, &name, &symbol, &7);
-        client.mint(&recipient, &1_000_000_000i128);
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+        client.mint(&admin, &recipient, &1_000_000_000i128);
 
         assert_eq!(client.balance(&recipient), 1_000_000_000i128);
         assert_eq!(client.total_supply(), 1_000_000_000i128);
@@ -240,8 +1088,9 @@ mod test {
         let symbol = String::from_str(&env, "SST");
 
         env.mock_all_auths();
-        client.initialize(&admin, &name, &symbol, &7);
-        client.mint(&user1, &1_000_000_000i128);
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+        client.mint(&admin, &user1, &1_000_000_000i128);
         client.transfer(&user1, &user2, &300_000_000i128);
 
         assert_eq!(client.balance(&user1), 700_000_000i128);
@@ -263,8 +1112,9 @@ mod test {
         let symbol = String::from_str(&env, "SST");
 
         env.mock_all_auths();
-        client.initialize(&admin, &name, &symbol, &7);
-        client.mint(&user1, &100i128);
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+        client.mint(&admin, &user1, &100i128);
         client.transfer(&user1, &user2, &500i128);
     }
 
@@ -280,7 +1130,367 @@ mod test {
         let symbol = String::from_str(&env, "SST");
 
         env.mock_all_auths();
-        client.initialize(&admin, &name, &symbol, &7);
-        client.initialize(&admin, &name, &symbol, &7);
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.initialize(&admin, &name, &symbol, &7, &None);
+    }
+
+    #[test]
+    fn test_burn() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardTokenContract);
+        let client = RewardTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let name = String::from_str(&env, "Stellar Support Token");
+        let symbol = String::from_str(&env, "SST");
+
+        env.mock_all_auths();
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+        client.mint(&admin, &user, &1_000i128);
+        client.burn(&user, &400i128);
+
+        assert_eq!(client.balance(&user), 600i128);
+        assert_eq!(client.total_supply(), 600i128);
+    }
+
+    #[test]
+    fn test_clawback_with_debt() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardTokenContract);
+        let client = RewardTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+        let name = String::from_str(&env, "Stellar Support Token");
+        let symbol = String::from_str(&env, "SST");
+
+        env.mock_all_auths();
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+        client.mint(&admin, &user1, &1_000i128);
+        client.transfer(&user1, &user2, &700i128);
+
+        assert_eq!(client.clawback(&admin, &user1, &1_000i128), 300i128);
+        assert_eq!(client.balance(&user1), 0i128);
+        assert_eq!(client.debt(&user1), 700i128);
+        assert_eq!(client.total_supply(), 700i128);
+
+        // Future mints settle the debt first
+        client.mint(&admin, &user1, &1_000i128);
+        assert_eq!(client.debt(&user1), 0i128);
+        assert_eq!(client.balance(&user1), 300i128);
+        assert_eq!(client.total_supply(), 1_000i128);
+    }
+
+    #[test]
+    fn test_stake_voting_power_history() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardTokenContract);
+        let client = RewardTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let name = String::from_str(&env, "Stellar Support Token");
+        let symbol = String::from_str(&env, "SST");
+
+        // Scaled so the lock bonus divides evenly
+        let amount = MAX_LOCK_LEDGERS as i128 * 10;
+
+        env.mock_all_auths();
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+        client.mint(&admin, &user, &(amount * 2));
+
+        env.ledger().set_sequence_number(100);
+        client.stake(&user, &amount, &50_000);
+        assert_eq!(client.balance(&user), amount);
+
+        // Bonus of 10 per remaining lock ledger, decaying to 1x at unlock
+        assert_eq!(client.voting_power(&user, &99), 0i128);
+        assert_eq!(client.voting_power(&user, &100), amount + 500_000);
+        assert_eq!(client.voting_power(&user, &25_100), amount + 250_000);
+        assert_eq!(client.voting_power(&user, &50_100), amount);
+
+        env.ledger().set_sequence_number(50_100);
+        assert_eq!(client.unstake(&user), amount);
+        assert_eq!(client.balance(&user), amount * 2);
+
+        // History is preserved after unstaking
+        assert_eq!(client.voting_power(&user, &50_100), 0i128);
+        assert_eq!(client.staked_at(&user, &25_100), amount);
+        assert_eq!(client.staked_at(&user, &50_100), 0i128);
+        assert_eq!(client.voting_power(&user, &25_100), amount + 250_000);
+        assert_eq!(client.total_staked_at(&150), amount);
+        assert_eq!(client.total_staked_at(&50_100), 0i128);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stake is still locked")]
+    fn test_unstake_before_unlock() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardTokenContract);
+        let client = RewardTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let name = String::from_str(&env, "Stellar Support Token");
+        let symbol = String::from_str(&env, "SST");
+
+        env.mock_all_auths();
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+        client.mint(&admin, &user, &1_000i128);
+        client.stake(&user, &1_000i128, &1_000);
+
+        env.ledger().set_sequence_number(999);
+        client.unstake(&user);
+    }
+
+    #[test]
+    fn test_balance_checkpoints() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardTokenContract);
+        let client = RewardTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+        let name = String::from_str(&env, "Stellar Support Token");
+        let symbol = String::from_str(&env, "SST");
+
+        env.mock_all_auths();
+        env.ledger().set_sequence_number(10);
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+        client.mint(&admin, &user1, &1_000i128);
+
+        env.ledger().set_sequence_number(20);
+        client.transfer(&user1, &user2, &400i128);
+        client.mint(&admin, &user2, &100i128);
+
+        env.ledger().set_sequence_number(30);
+        client.burn(&user2, &200i128);
+
+        assert_eq!(client.balance_at(&user1, &9), 0i128);
+        assert_eq!(client.balance_at(&user1, &15), 1_000i128);
+        assert_eq!(client.balance_at(&user1, &20), 600i128);
+        assert_eq!(client.balance_at(&user2, &25), 500i128);
+        assert_eq!(client.balance_at(&user2, &30), 300i128);
+
+        assert_eq!(client.total_supply_at(&10), 1_000i128);
+        assert_eq!(client.total_supply_at(&20), 1_100i128);
+        assert_eq!(client.total_supply_at(&100), 900i128);
+    }
+
+    #[test]
+    fn test_supply_cap() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardTokenContract);
+        let client = RewardTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let name = String::from_str(&env, "Stellar Support Token");
+        let symbol = String::from_str(&env, "SST");
+
+        env.mock_all_auths();
+        client.initialize(&admin, &name, &symbol, &7, &Some(1_000i128));
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+        client.mint(&admin, &user, &600i128);
+
+        // A plain mint crossing the cap is rejected; the explicit partial path fills the room
+        assert!(client.try_mint(&admin, &user, &1_000i128).is_err());
+        assert_eq!(client.mint_up_to(&admin, &user, &1_000i128), 400i128);
+        assert_eq!(client.balance(&user), 1_000i128);
+        assert_eq!(client.minter_allowance(&admin), Some(MINT_ALLOWANCE - 1_000));
+
+        assert_eq!(client.supply_cap(), Some(1_000i128));
+        assert!(client.try_mint(&admin, &user, &1i128).is_err());
+        assert!(client.try_mint_up_to(&admin, &user, &1i128).is_err());
+        assert_eq!(client.total_supply(), 1_000i128);
+    }
+
+    #[test]
+    fn test_mint_rate_limits() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardTokenContract);
+        let client = RewardTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+        let name = String::from_str(&env, "Stellar Support Token");
+        let symbol = String::from_str(&env, "SST");
+
+        env.mock_all_auths();
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+        client.set_mint_limits(&100, &1_000i128, &600i128);
+
+        client.mint(&admin, &user1, &600i128);
+        assert!(client.try_mint(&admin, &user1, &1i128).is_err());
+
+        client.mint(&admin, &user2, &400i128);
+        assert!(client.try_mint(&admin, &user2, &1i128).is_err());
+
+        // Limits reset in the next window
+        env.ledger().set_sequence_number(100);
+        client.mint(&admin, &user1, &600i128);
+        assert_eq!(client.balance(&user1), 1_200i128);
+    }
+
+    #[test]
+    fn test_multiple_minters() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardTokenContract);
+        let client = RewardTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let campaigns = Address::generate(&env);
+        let quests = Address::generate(&env);
+        let user = Address::generate(&env);
+        let name = String::from_str(&env, "Stellar Support Token");
+        let symbol = String::from_str(&env, "SST");
+
+        env.mock_all_auths();
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&campaigns, &1_000i128);
+        client.add_minter(&quests, &500i128);
+
+        client.mint(&campaigns, &user, &800i128);
+        client.mint(&quests, &user, &500i128);
+        assert_eq!(client.balance(&user), 1_300i128);
+        assert_eq!(client.minter_allowance(&campaigns), Some(200i128));
+        assert!(client.try_mint(&quests, &user, &1i128).is_err());
+
+        // Clawback is limited to the minter's own issuance and never refunds its allowance
+        assert_eq!(client.clawback(&quests, &user, &1_300i128), 500i128);
+        assert_eq!(client.clawback(&campaigns, &user, &300i128), 300i128);
+        assert_eq!(client.balance(&user), 500i128);
+        assert_eq!(client.minter_allowance(&campaigns), Some(200i128));
+        assert_eq!(client.minter_allowance(&quests), Some(0i128));
+        assert_eq!(client.debt(&user), 0);
+
+        client.remove_minter(&quests);
+        assert_eq!(client.minters(), soroban_sdk::vec![&env, campaigns]);
+        assert!(client.try_mint(&quests, &user, &1i128).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Not a minter")]
+    fn test_mint_requires_minter() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardTokenContract);
+        let client = RewardTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let name = String::from_str(&env, "Stellar Support Token");
+        let symbol = String::from_str(&env, "SST");
+
+        env.mock_all_auths();
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.mint(&user, &user, &100i128);
+    }
+
+    #[test]
+    fn test_vesting_schedule() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardTokenContract);
+        let client = RewardTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let name = String::from_str(&env, "Stellar Support Token");
+        let symbol = String::from_str(&env, "SST");
+
+        env.mock_all_auths();
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+
+        env.ledger().set_sequence_number(100);
+        client.mint_vested(&admin, &user, &1_000i128, &100, &400);
+        assert_eq!(client.balance(&user), 0);
+        assert_eq!(client.total_supply(), 1_000i128);
+        assert_eq!(client.locked_balance(&user), 1_000i128);
+
+        // Nothing unlocks before the cliff
+        env.ledger().set_sequence_number(150);
+        assert_eq!(client.unlocked_balance(&user), 0);
+        assert!(client.try_claim_vested(&user).is_err());
+
+        // Linear release after the cliff
+        env.ledger().set_sequence_number(300);
+        assert_eq!(client.unlocked_balance(&user), 500i128);
+        assert_eq!(client.claim_vested(&user), 500i128);
+        assert_eq!(client.balance(&user), 500i128);
+        assert_eq!(client.locked_balance(&user), 500i128);
+
+        env.ledger().set_sequence_number(600);
+        assert_eq!(client.claim_vested(&user), 500i128);
+        assert_eq!(client.balance(&user), 1_000i128);
+        assert_eq!(client.vesting_schedules(&user).len(), 0);
+    }
+
+    #[test]
+    fn test_clawback_takes_staked_tokens() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardTokenContract);
+        let client = RewardTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let name = String::from_str(&env, "Stellar Support Token");
+        let symbol = String::from_str(&env, "SST");
+
+        env.mock_all_auths();
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+        client.mint(&admin, &user, &1_000i128);
+
+        env.ledger().set_sequence_number(100);
+        client.stake(&user, &800i128, &1_000);
+
+        // Liquid balance goes first, the remainder comes out of the stake
+        assert_eq!(client.clawback(&admin, &user, &500i128), 500i128);
+        assert_eq!(client.balance(&user), 0);
+        assert_eq!(client.get_stake(&user).unwrap().amount, 500i128);
+        assert_eq!(client.total_staked_at(&100), 500i128);
+        assert_eq!(client.debt(&user), 0);
+
+        // Nothing is left to unstake beyond what survived the clawback
+        env.ledger().set_sequence_number(1_100);
+        assert_eq!(client.unstake(&user), 500i128);
+        assert_eq!(client.total_supply(), 500i128);
+    }
+
+    #[test]
+    fn test_clawback_takes_vesting_tokens() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, RewardTokenContract);
+        let client = RewardTokenContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let name = String::from_str(&env, "Stellar Support Token");
+        let symbol = String::from_str(&env, "SST");
+
+        env.mock_all_auths();
+        client.initialize(&admin, &name, &symbol, &7, &None);
+        client.add_minter(&admin, &MINT_ALLOWANCE);
+
+        env.ledger().set_sequence_number(100);
+        client.mint(&admin, &user, &200i128);
+        client.mint_vested(&admin, &user, &1_000i128, &0, &1_000);
+
+        // The locked position goes first, then the liquid balance
+        assert_eq!(client.clawback(&admin, &user, &1_100i128), 1_100i128);
+        assert_eq!(client.locked_balance(&user), 0);
+        assert_eq!(client.balance(&user), 100i128);
+        assert_eq!(client.total_supply(), 100i128);
+        assert_eq!(client.debt(&user), 0);
     }
 }
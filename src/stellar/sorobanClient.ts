@@ -165,7 +165,10 @@ export const createCampaign = async (
 export const donateToCampaign = async (
   campaignId: number,
   amount: number,
-  donorAddress: string
+  donorAddress: string,
+  message?: string,
+  anonymous: boolean = false,
+  tierId?: number
 ): Promise<TransactionResult> => {
   try {
 // Get account from network
@@ -187,7 +190,10 @@ export const donateToCampaign = async (
           "donate",
           nativeToScVal(campaignId, { type: "u64" }),
           Address.fromString(donorAddress).toScVal(),
-          nativeToScVal(amountInStroops, { type: "i128" })
+          nativeToScVal(amountInStroops, { type: "i128" }),
+          message ? nativeToScVal(message, { type: "string" }) : nativeToScVal(null),
+          nativeToScVal(anonymous),
+          tierId !== undefined ? nativeToScVal(tierId, { type: "u32" }) : nativeToScVal(null)
         )
       )
       .setTimeout(30)
@@ -330,7 +336,7 @@ export const getAllCampaigns = async (): Promise<Campaign[]> => {
       fee: BASE_FEE,
       networkPassphrase: TESTNET_NETWORK_PASSPHRASE,
     })
-      .addOperation(contract.call("get_all_campaigns"))
+      .addOperation(contract.call("get_all_campaigns", nativeToScVal(false)))
       .setTimeout(30)
       .build();
 